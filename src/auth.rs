@@ -1,10 +1,101 @@
+use crate::domain::{Credentials, Forge, Org, Repo, uses_forge_token};
 use anyhow::Context;
 use std::env::VarError;
 use std::process::Command;
 
 const TOKEN_ENV_VAR: &str = "UNRELEASED_GH_TOKEN";
+const GITLAB_TOKEN_ENV_VAR: &str = "UNRELEASED_GITLAB_TOKEN";
+const GITEA_TOKEN_ENV_VAR: &str = "UNRELEASED_GITEA_TOKEN";
+const HOST_TOKEN_ENV_VAR_PREFIX: &str = "UNRELEASED_TOKEN_";
 
-pub fn get_token() -> anyhow::Result<String> {
+// tokens are only fetched for the forges and hosts that are in use; a host specific token
+// removes the need for the forge-wide one, which is only used for the forge's default host
+pub fn get_credentials(repos: &[Repo]) -> anyhow::Result<Credentials> {
+    let mut credentials = Credentials::default();
+
+    for repo in repos.iter().filter(|repo| repo.forge != Forge::Local) {
+        add_host_token(&mut credentials, repo.host())?;
+    }
+
+    let needs_forge_token = |forge: Forge| {
+        repos.iter().any(|repo| {
+            repo.forge == forge
+                && uses_forge_token(forge, repo.host())
+                && !credentials.host_tokens.contains_key(repo.host())
+        })
+    };
+
+    if needs_forge_token(Forge::GitHub) {
+        credentials.github_token = Some(get_token()?);
+    }
+
+    // GitLab and Gitea projects can be public, so tokens are optional for them
+    if needs_forge_token(Forge::GitLab) {
+        credentials.gitlab_token = get_optional_env_var(GITLAB_TOKEN_ENV_VAR)?;
+    }
+
+    if needs_forge_token(Forge::Gitea) {
+        credentials.gitea_token = get_optional_env_var(GITEA_TOKEN_ENV_VAR)?;
+    }
+
+    Ok(credentials)
+}
+
+// orgs are only supported for GitHub
+pub fn get_org_credentials(orgs: &[Org]) -> anyhow::Result<Credentials> {
+    let mut credentials = Credentials::default();
+
+    for org in orgs {
+        add_host_token(&mut credentials, org.host())?;
+    }
+
+    if orgs.iter().any(|org| {
+        uses_forge_token(Forge::GitHub, org.host())
+            && !credentials.host_tokens.contains_key(org.host())
+    }) {
+        credentials.github_token = Some(get_token()?);
+    }
+
+    Ok(credentials)
+}
+
+fn add_host_token(credentials: &mut Credentials, host: &str) -> anyhow::Result<()> {
+    if credentials.host_tokens.contains_key(host) {
+        return Ok(());
+    }
+
+    if let Some(token) = get_optional_env_var(&get_host_token_env_var(host))? {
+        credentials.host_tokens.insert(host.to_string(), token);
+    }
+
+    Ok(())
+}
+
+// eg. "UNRELEASED_TOKEN_GIT_EXAMPLE_COM" for "git.example.com"
+fn get_host_token_env_var(host: &str) -> String {
+    let host: String = host
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("{}{}", HOST_TOKEN_ENV_VAR_PREFIX, host)
+}
+
+fn get_optional_env_var(name: &str) -> anyhow::Result<Option<String>> {
+    match std::env::var(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(VarError::NotPresent) => Ok(None),
+        Err(VarError::NotUnicode(_)) => Err(anyhow::anyhow!("{} is not valid unicode", name)),
+    }
+}
+
+fn get_token() -> anyhow::Result<String> {
     let token = std::env::var(TOKEN_ENV_VAR).or_else(|err| match err {
         VarError::NotPresent => get_token_from_gh().context(format!(
             r#"couldn't get a GitHub authentication token
@@ -1,6 +1,7 @@
+use std::num::{NonZeroU64, NonZeroUsize};
 use std::path::PathBuf;
 
-use crate::domain::OutputFormat;
+use crate::domain::{GitHubApi, OutputFormat};
 use clap::{Parser, Subcommand};
 
 const NOT_PROVIDED: &str = "<NOT PROVIDED>";
@@ -49,6 +50,136 @@ pub enum UnreleasedCommand {
         /// Path to custom HTML template file
         #[arg(long = "html-template", value_name = "PATH")]
         html_template_path: Option<PathBuf>,
+        /// Path to custom Markdown template file
+        #[arg(long = "markdown-template", value_name = "PATH")]
+        markdown_template_path: Option<PathBuf>,
+        /// Path to the template file to render (required for the template output format)
+        #[arg(
+            long = "template",
+            value_name = "PATH",
+            required_if_eq("output_format", "template")
+        )]
+        template_path: Option<PathBuf>,
+        /// Path for the output file of the template output format (defaults to stdout)
+        #[arg(long = "template-output", value_name = "PATH")]
+        template_output_path: Option<PathBuf>,
+        /// Maximum number of commits to show for repos that have never been released
+        #[arg(
+            long = "never-released-commits",
+            value_name = "NUM",
+            default_value_t = 10
+        )]
+        never_released_max_commits: usize,
+        /// Only show commits from the last N days for repos that have never been released
+        #[arg(long = "never-released-days", value_name = "DAYS")]
+        never_released_max_age_days: Option<u32>,
+        /// Generate a report even if commit logs couldn't be fetched for some repos (exits with code 3 if that happens)
+        #[arg(long = "keep-going")]
+        keep_going: bool,
+        /// Don't use cached API responses, or cache new ones
+        #[arg(long = "no-cache")]
+        no_cache: bool,
+        /// Directory to cache API responses in (defaults to <YOUR_CACHE_DIR>/unreleased)
+        #[arg(long = "cache-dir", value_name = "PATH", conflicts_with = "no_cache")]
+        cache_dir: Option<PathBuf>,
+        /// API to fetch GitHub repos with (overrides the config's github_api; defaults to auto)
+        #[arg(long = "github-api", value_name = "API")]
+        github_api: Option<GitHubApi>,
+        /// Maximum number of repos to fetch at the same time (overrides the config's concurrency; defaults to 20)
+        #[arg(long = "concurrency", value_name = "NUM")]
+        concurrency: Option<NonZeroUsize>,
+        /// Seconds to wait for a response before retrying a request (overrides the config's request_timeout_secs; defaults to 30)
+        #[arg(long = "request-timeout", value_name = "SECS")]
+        request_timeout_secs: Option<NonZeroU64>,
+        /// Seconds to wait for a connection to a forge (overrides the config's connect_timeout_secs; defaults to 10)
+        #[arg(long = "connect-timeout", value_name = "SECS")]
+        connect_timeout_secs: Option<NonZeroU64>,
+        /// Seconds after which to stop fetching, and report repos not fetched by then as timed out (overrides the config's deadline_secs)
+        #[arg(long = "deadline", value_name = "SECS")]
+        deadline_secs: Option<NonZeroU64>,
+        /// Don't show progress, or the remaining API quotas, on stderr (errors are still shown)
+        #[arg(long = "quiet", short = 'q', conflicts_with = "progress")]
+        quiet: bool,
+        /// Show progress on stderr even if it isn't a terminal (it's shown on terminals by default)
+        #[arg(long = "progress")]
+        progress: bool,
+    },
+    /// Manage unreleased's config file
+    #[command(name = "config")]
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Manage the cache of API responses
+    #[command(name = "cache")]
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// Remove all cached API responses
+    #[command(name = "clear")]
+    Clear {
+        /// Directory API responses are cached in (defaults to <YOUR_CACHE_DIR>/unreleased)
+        #[arg(long = "cache-dir", value_name = "PATH")]
+        cache_dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Write a commented starter config file
+    #[command(name = "init")]
+    Init {
+        /// Path to the unreleased's file (defaults to <YOUR_CONFIG_DIR>/unreleased/unreleased.toml)
+        #[arg(long = "config-path", short = 'c', value_name = "PATH")]
+        config_file_path: Option<PathBuf>,
+        /// Overwrite the config file if it already exists
+        #[arg(long = "force")]
+        force: bool,
+    },
+    /// Check the config file for errors, without making any network requests
+    #[command(name = "validate")]
+    Validate {
+        /// Path to the unreleased's file (defaults to <YOUR_CONFIG_DIR>/unreleased/unreleased.toml)
+        #[arg(long = "config-path", short = 'c', value_name = "PATH")]
+        config_file_path: Option<PathBuf>,
+    },
+    /// Add a repo to the config file
+    #[command(name = "add")]
+    Add {
+        /// Repo to add, in the format "owner/repo"
+        #[arg(value_name = "REPO")]
+        repo: String,
+        /// Head ref to use when generating the repo's commit log
+        #[arg(long = "head-ref", value_name = "REF")]
+        head_ref: Option<String>,
+        /// Path to the unreleased's file (defaults to <YOUR_CONFIG_DIR>/unreleased/unreleased.toml)
+        #[arg(long = "config-path", short = 'c', value_name = "PATH")]
+        config_file_path: Option<PathBuf>,
+    },
+    /// Remove a repo from the config file
+    #[command(name = "remove")]
+    Remove {
+        /// Repo to remove, in the format "owner/repo" (or its path, for local repos)
+        #[arg(value_name = "REPO")]
+        repo: String,
+        /// Path to the unreleased's file (defaults to <YOUR_CONFIG_DIR>/unreleased/unreleased.toml)
+        #[arg(long = "config-path", short = 'c', value_name = "PATH")]
+        config_file_path: Option<PathBuf>,
+    },
+    /// List the repos in the config file, with defaults and group settings applied
+    #[command(name = "list")]
+    List {
+        /// Path to the unreleased's file (defaults to <YOUR_CONFIG_DIR>/unreleased/unreleased.toml)
+        #[arg(long = "config-path", short = 'c', value_name = "PATH")]
+        config_file_path: Option<PathBuf>,
+        /// Regex to use for filtering repos
+        #[arg(long = "filter", short = 'f', value_name = "REGEX")]
+        repo_filter: Option<String>,
     },
 }
 
@@ -63,7 +194,30 @@ impl std::fmt::Display for Args {
                 html_output_path,
                 html_title,
                 html_template_path,
+                markdown_template_path,
+                template_path,
+                template_output_path,
+                never_released_max_commits,
+                never_released_max_age_days,
+                keep_going,
+                no_cache,
+                cache_dir,
+                github_api,
+                concurrency,
+                request_timeout_secs,
+                connect_timeout_secs,
+                deadline_secs,
+                quiet,
+                progress,
             } => {
+                let progress = if *quiet {
+                    "off"
+                } else if *progress {
+                    "on"
+                } else {
+                    "auto"
+                };
+
                 let flags_based_on_output = match output_format {
                     OutputFormat::Stdout => format!(
                         r#"
@@ -86,6 +240,30 @@ template path:          {}
                                 .unwrap_or(NOT_PROVIDED.to_string())
                         )
                     }
+                    OutputFormat::Json => String::new(),
+                    OutputFormat::Markdown => format!(
+                        r#"
+template path:          {}
+"#,
+                        markdown_template_path
+                            .as_ref()
+                            .map(|p| p.to_string_lossy().to_string())
+                            .unwrap_or(NOT_PROVIDED.to_string())
+                    ),
+                    OutputFormat::Template => format!(
+                        r#"
+template path:          {}
+output path:            {}
+"#,
+                        template_path
+                            .as_ref()
+                            .map(|p| p.to_string_lossy().to_string())
+                            .unwrap_or(NOT_PROVIDED.to_string()),
+                        template_output_path
+                            .as_ref()
+                            .map(|p| p.to_string_lossy().to_string())
+                            .unwrap_or("stdout".to_string())
+                    ),
                 };
 
                 format!(
@@ -93,6 +271,17 @@ template path:          {}
 command:                report
 config file path:       {}
 repo filter:            {}
+never released commits: {}
+never released days:    {}
+keep going:             {}
+no cache:               {}
+cache dir:              {}
+github api:             {}
+concurrency:            {}
+request timeout:        {}
+connect timeout:        {}
+deadline:               {}
+progress:               {}
 output format:          {}{}
 "#,
                     config_file_path
@@ -100,10 +289,108 @@ output format:          {}{}
                         .map(|p| p.to_string_lossy().to_string())
                         .unwrap_or(NOT_PROVIDED.to_string()),
                     repo_filter.as_deref().unwrap_or(NOT_PROVIDED),
+                    never_released_max_commits,
+                    never_released_max_age_days
+                        .map(|d| d.to_string())
+                        .unwrap_or(NOT_PROVIDED.to_string()),
+                    keep_going,
+                    no_cache,
+                    cache_dir
+                        .as_ref()
+                        .map(|p| p.to_string_lossy().to_string())
+                        .unwrap_or(NOT_PROVIDED.to_string()),
+                    github_api
+                        .map(|api| api.to_string())
+                        .unwrap_or(NOT_PROVIDED.to_string()),
+                    concurrency
+                        .map(|c| c.to_string())
+                        .unwrap_or(NOT_PROVIDED.to_string()),
+                    request_timeout_secs
+                        .map(|t| format!("{}s", t))
+                        .unwrap_or(NOT_PROVIDED.to_string()),
+                    connect_timeout_secs
+                        .map(|t| format!("{}s", t))
+                        .unwrap_or(NOT_PROVIDED.to_string()),
+                    deadline_secs
+                        .map(|d| format!("{}s", d))
+                        .unwrap_or(NOT_PROVIDED.to_string()),
+                    progress,
                     output_format,
                     flags_based_on_output
                 )
             }
+            UnreleasedCommand::Config { command } => {
+                let (subcommand, config_file_path, extra) = match command {
+                    ConfigCommand::Init {
+                        config_file_path,
+                        force,
+                    } => (
+                        "init",
+                        config_file_path,
+                        format!("force:                  {}\n", force),
+                    ),
+                    ConfigCommand::Validate { config_file_path } => {
+                        ("validate", config_file_path, String::new())
+                    }
+                    ConfigCommand::Add {
+                        repo,
+                        head_ref,
+                        config_file_path,
+                    } => (
+                        "add",
+                        config_file_path,
+                        format!(
+                            "repo:                   {}\nhead ref:               {}\n",
+                            repo,
+                            head_ref.as_deref().unwrap_or(NOT_PROVIDED)
+                        ),
+                    ),
+                    ConfigCommand::Remove {
+                        repo,
+                        config_file_path,
+                    } => (
+                        "remove",
+                        config_file_path,
+                        format!("repo:                   {}\n", repo),
+                    ),
+                    ConfigCommand::List {
+                        config_file_path,
+                        repo_filter,
+                    } => (
+                        "list",
+                        config_file_path,
+                        format!(
+                            "repo filter:            {}\n",
+                            repo_filter.as_deref().unwrap_or(NOT_PROVIDED)
+                        ),
+                    ),
+                };
+
+                format!(
+                    r#"
+command:                config {}
+config file path:       {}
+{}"#,
+                    subcommand,
+                    config_file_path
+                        .as_ref()
+                        .map(|p| p.to_string_lossy().to_string())
+                        .unwrap_or(NOT_PROVIDED.to_string()),
+                    extra
+                )
+            }
+            UnreleasedCommand::Cache {
+                command: CacheCommand::Clear { cache_dir },
+            } => format!(
+                r#"
+command:                cache clear
+cache dir:              {}
+"#,
+                cache_dir
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or(NOT_PROVIDED.to_string())
+            ),
         };
 
         f.write_str(&output)
@@ -3,6 +3,9 @@ use anyhow::Context;
 use etcetera::{BaseStrategy, choose_base_strategy};
 use regex::Regex;
 use std::path::{Path, PathBuf};
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, value};
+
+const STARTER_CONFIG: &str = include_str!("assets/starter-config.toml");
 
 pub fn get_default_config_path() -> anyhow::Result<PathBuf> {
     let strategy = choose_base_strategy()
@@ -18,13 +21,392 @@ pub fn get_default_config_path() -> anyhow::Result<PathBuf> {
     Ok(config_dir.join("unreleased.toml"))
 }
 
+/// The provided path, or the default one if none was provided
+pub fn get_config_path(config_file_path: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    match config_file_path {
+        Some(path) => Ok(path),
+        None => get_default_config_path(),
+    }
+}
+
 pub fn get_from_file<P>(path: P, repo_filter: Option<&Regex>) -> anyhow::Result<Config>
 where
     P: AsRef<Path>,
 {
-    let contents = std::fs::read_to_string(&path).context("couldn't read file")?;
+    read_config(path.as_ref(), repo_filter).with_context(|| {
+        format!(
+            "couldn't get config from file \"{}\"",
+            path.as_ref().to_string_lossy()
+        )
+    })
+}
 
-    let config = parse_config(&contents, repo_filter)?;
+fn read_config(path: &Path, repo_filter: Option<&Regex>) -> anyhow::Result<Config> {
+    let contents = std::fs::read_to_string(path).context("couldn't read file")?;
+
+    let config = parse_config(&contents, Some(path), repo_filter)?;
 
     Ok(config)
 }
+
+pub fn write_starter_file<P>(path: P, force: bool) -> anyhow::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    if path.exists() && !force {
+        anyhow::bail!("file already exists; use --force to overwrite it");
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("couldn't create directory {:?}", parent))?;
+    }
+    std::fs::write(path, STARTER_CONFIG).context("couldn't write file")?;
+
+    Ok(())
+}
+
+pub fn add_repo_to_file<P>(path: P, repo: &str, head_ref: Option<&str>) -> anyhow::Result<()>
+where
+    P: AsRef<Path>,
+{
+    update_file(path, |contents| add_repo(contents, repo, head_ref))
+}
+
+pub fn remove_repo_from_file<P>(path: P, repo: &str) -> anyhow::Result<()>
+where
+    P: AsRef<Path>,
+{
+    update_file(path, |contents| remove_repo(contents, repo))
+}
+
+// the updated contents are validated before being written, so that an edit never leaves behind a
+// config that "report" would reject
+fn update_file<P, F>(path: P, update: F) -> anyhow::Result<()>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&str) -> anyhow::Result<String>,
+{
+    let contents = std::fs::read_to_string(&path).context("couldn't read file")?;
+    let updated = update(&contents)?;
+    parse_config(&updated, Some(path.as_ref()), None)
+        .context("the updated config would be invalid")?;
+    std::fs::write(&path, updated).context("couldn't write file")?;
+
+    Ok(())
+}
+
+// toml_edit is used (instead of toml) so that comments and formatting are left untouched; repeated
+// entries are caught by the validation in update_file, since a repo can be listed once per head ref
+fn add_repo(contents: &str, repo: &str, head_ref: Option<&str>) -> anyhow::Result<String> {
+    let mut doc: DocumentMut = contents.parse().context("couldn't parse TOML")?;
+
+    let repos = doc
+        .entry("repos")
+        .or_insert_with(|| Item::ArrayOfTables(ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .context("\"repos\" needs to be an array of tables")?;
+
+    let mut entry = Table::new();
+    entry.insert("repo", value(repo));
+    if let Some(head_ref) = head_ref {
+        entry.insert("head_ref", value(head_ref));
+    }
+    repos.push(entry);
+
+    Ok(doc.to_string())
+}
+
+fn remove_repo(contents: &str, repo: &str) -> anyhow::Result<String> {
+    let mut doc: DocumentMut = contents.parse().context("couldn't parse TOML")?;
+    let mut removed = 0;
+    let mut orphaned_decor = Vec::new();
+
+    if let Some(repos) = doc
+        .get_mut("repos")
+        .and_then(|item| item.as_array_of_tables_mut())
+    {
+        let (count, decor) = remove_matching_entries(repos, repo);
+        removed += count;
+        orphaned_decor.extend(decor);
+    }
+
+    if let Some(groups) = doc
+        .get_mut("groups")
+        .and_then(|item| item.as_array_of_tables_mut())
+    {
+        for group in groups.iter_mut() {
+            if let Some(repos) = group
+                .get_mut("repos")
+                .and_then(|item| item.as_array_of_tables_mut())
+            {
+                let (count, decor) = remove_matching_entries(repos, repo);
+                removed += count;
+                orphaned_decor.extend(decor);
+            }
+        }
+    }
+
+    if removed == 0 {
+        anyhow::bail!("repo \"{}\" is not present in the config", repo);
+    }
+
+    // comments above removed entries that had no entry after them to move onto are kept at the
+    // end of the file
+    let orphaned_comments = orphaned_decor
+        .iter()
+        .filter(|decor| !decor.trim().is_empty())
+        .map(String::as_str)
+        .collect::<String>();
+    if !orphaned_comments.is_empty() {
+        let trailing = doc.trailing().as_str().unwrap_or_default().to_string();
+        doc.set_trailing(format!("{}{}", trailing, orphaned_comments));
+    }
+
+    Ok(doc.to_string())
+}
+
+// the decor in front of a removed entry (eg. a comment heading the file) is moved onto the entry
+// after it; the decor of a removed entry that's last in the array is handed back to the caller
+fn remove_matching_entries(repos: &mut ArrayOfTables, repo: &str) -> (usize, Option<String>) {
+    let mut removed = 0;
+    let mut pending_decor: Option<String> = None;
+
+    let mut i = 0;
+    while let Some(entry) = repos.get_mut(i) {
+        let prefix = entry
+            .decor()
+            .prefix()
+            .and_then(|prefix| prefix.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        if entry_matches(entry, repo) {
+            pending_decor = Some(join_decor(pending_decor, &prefix));
+            repos.remove(i);
+            removed += 1;
+            continue;
+        }
+
+        if let Some(decor) = pending_decor.take() {
+            entry
+                .decor_mut()
+                .set_prefix(join_decor(Some(decor), &prefix));
+        }
+        i += 1;
+    }
+
+    (removed, pending_decor)
+}
+
+// leading blank lines of the later decor are dropped, since the earlier one already separates the
+// entry from whatever comes before it
+fn join_decor(earlier: Option<String>, later: &str) -> String {
+    match earlier {
+        Some(earlier) => format!("{}{}", earlier, later.trim_start_matches(['\r', '\n'])),
+        None => later.to_string(),
+    }
+}
+
+// matches entries the way repos are compared when checking for repeated entries: names of forge
+// repos ignore case, paths of local repos don't
+fn entry_matches(entry: &Table, repo: &str) -> bool {
+    let matches_name = entry
+        .get("repo")
+        .and_then(|item| item.as_str())
+        .is_some_and(|name| name.eq_ignore_ascii_case(repo));
+    let matches_path = entry.get("path").and_then(|item| item.as_str()) == Some(repo);
+
+    matches_name || matches_path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    const CONFIG: &str = r#"# repos I maintain
+[[repos]]
+repo = "owner/repo-a"  # the main one
+
+[[repos]]
+# needs a different branch
+repo = "owner/repo-b"
+head_ref = "develop"
+
+[[groups]]
+name = "payments"
+
+[[groups.repos]]
+repo = "owner/repo-c"
+"#;
+
+    //-------------//
+    //  SUCCESSES  //
+    //-------------//
+
+    #[test]
+    fn adding_a_repo_keeps_comments_and_formatting() -> anyhow::Result<()> {
+        // GIVEN
+        // WHEN
+        let result = add_repo(CONFIG, "owner/repo-d", Some("main"))?;
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        # repos I maintain
+        [[repos]]
+        repo = "owner/repo-a"  # the main one
+
+        [[repos]]
+        # needs a different branch
+        repo = "owner/repo-b"
+        head_ref = "develop"
+
+        [[repos]]
+        repo = "owner/repo-d"
+        head_ref = "main"
+
+        [[groups]]
+        name = "payments"
+
+        [[groups.repos]]
+        repo = "owner/repo-c"
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn adding_a_repo_to_a_config_without_repos_works() -> anyhow::Result<()> {
+        // GIVEN
+        let config = "keep_going = true\n";
+
+        // WHEN
+        let result = add_repo(config, "owner/repo-a", None)?;
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        keep_going = true
+
+        [[repos]]
+        repo = "owner/repo-a"
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn removing_a_repo_keeps_comments_and_formatting() -> anyhow::Result<()> {
+        // GIVEN
+        // WHEN
+        let result = remove_repo(CONFIG, "owner/repo-a")?;
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        # repos I maintain
+        [[repos]]
+        # needs a different branch
+        repo = "owner/repo-b"
+        head_ref = "develop"
+
+        [[groups]]
+        name = "payments"
+
+        [[groups.repos]]
+        repo = "owner/repo-c"
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn removing_a_repo_ignores_the_case_of_its_name() -> anyhow::Result<()> {
+        // GIVEN
+        // WHEN
+        let result = remove_repo(CONFIG, "Owner/Repo-B")?;
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        # repos I maintain
+        [[repos]]
+        repo = "owner/repo-a"  # the main one
+
+        [[groups]]
+        name = "payments"
+
+        [[groups.repos]]
+        repo = "owner/repo-c"
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn removing_the_only_repo_keeps_the_comment_above_it() -> anyhow::Result<()> {
+        // GIVEN
+        let config = r#"# repos I maintain
+[[repos]]
+repo = "owner/repo-a"
+"#;
+
+        // WHEN
+        let result = remove_repo(config, "owner/repo-a")?;
+
+        // THEN
+        assert_snapshot!(result, @"# repos I maintain");
+
+        Ok(())
+    }
+
+    #[test]
+    fn removing_a_grouped_repo_works() -> anyhow::Result<()> {
+        // GIVEN
+        // WHEN
+        let result = remove_repo(CONFIG, "owner/repo-c")?;
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        # repos I maintain
+        [[repos]]
+        repo = "owner/repo-a"  # the main one
+
+        [[repos]]
+        # needs a different branch
+        repo = "owner/repo-b"
+        head_ref = "develop"
+
+        [[groups]]
+        name = "payments"
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn starter_config_is_valid() -> anyhow::Result<()> {
+        // GIVEN
+        // WHEN
+        let result = parse_config(STARTER_CONFIG, None, None)?;
+
+        // THEN
+        assert_eq!(result.repos.len(), 1);
+
+        Ok(())
+    }
+
+    //------------//
+    //  FAILURES  //
+    //------------//
+
+    #[test]
+    fn removing_a_repo_that_is_not_present_fails() {
+        // GIVEN
+        // WHEN
+        let result =
+            remove_repo(CONFIG, "owner/repo-x").expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r#"repo "owner/repo-x" is not present in the config"#);
+    }
+}
@@ -1,41 +1,173 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    num::{NonZeroU64, NonZeroUsize},
+    ops::Range,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
-use super::repo::{RawRepo, Repo, RepoValidationError};
+use super::org::{Org, OrgValidationError, RawOrg};
+use super::repo::{
+    API_BASE_URL_ERROR, Baseline, Forge, RawRepo, Repo, RepoDefaults, RepoValidationError,
+    parse_api_base_url,
+};
 use anyhow::Context;
 use clap::ValueEnum;
 use regex::Regex;
 use serde::Deserialize;
+use toml::Spanned;
 
+pub const DEFAULT_CONCURRENCY: usize = 20;
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+// entries are kept spanned so that validation errors can point at their location in the file
 #[derive(Debug, Deserialize)]
 #[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
 struct RawConfig {
-    pub repos: Vec<RawRepo>,
+    pub api_base_url: Option<String>,
+    pub keep_going: Option<bool>,
+    pub github_api: Option<GitHubApi>,
+    pub concurrency: Option<usize>,
+    pub request_timeout_secs: Option<u64>,
+    pub connect_timeout_secs: Option<u64>,
+    pub deadline_secs: Option<u64>,
+    pub defaults: Option<RepoDefaults>,
+    #[serde(default)]
+    pub repos: Vec<Spanned<RawRepo>>,
+    pub groups: Option<Vec<Spanned<RawGroup>>>,
+    pub orgs: Option<Vec<Spanned<RawOrg>>>,
+}
+
+/// Repos bundled under a label; the group's settings override the top level defaults
+// the settings aren't flattened from RepoDefaults, since serde can't reject unknown keys (or keep
+// spans) for flattened structs
+#[derive(Debug, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+struct RawGroup {
+    pub name: String,
+    pub head_ref: Option<String>,
+    pub consider_prereleases: Option<bool>,
+    pub baseline: Option<Baseline>,
+    #[serde(default)]
+    pub repos: Vec<Spanned<RawRepo>>,
+}
+
+impl RawGroup {
+    fn defaults(&self) -> RepoDefaults {
+        RepoDefaults {
+            head_ref: self.head_ref.clone(),
+            consider_prereleases: self.consider_prereleases,
+            baseline: self.baseline,
+        }
+    }
 }
 
 #[derive(Debug)]
 #[cfg_attr(test, derive(serde::Serialize))]
 pub struct Config {
+    pub keep_going: bool,
+    pub github_api: Option<GitHubApi>,
+    pub concurrency: Option<NonZeroUsize>,
+    pub request_timeout_secs: Option<NonZeroU64>,
+    pub connect_timeout_secs: Option<NonZeroU64>,
+    pub deadline_secs: Option<NonZeroU64>,
     pub repos: Vec<Repo>,
+    /// Orgs whose repos are discovered at runtime, and added to repos
+    pub orgs: Vec<Org>,
+}
+
+impl Config {
+    /// Adds repos discovered from orgs, skipping the ones that are already present; explicitly
+    /// configured repos take precedence over discovered ones
+    pub fn add_discovered_repos(&mut self, discovered: Vec<Repo>, repo_filter: Option<&Regex>) {
+        for repo in discovered {
+            if repo_filter.is_some_and(|regex| !regex.is_match(&repo.to_string())) {
+                continue;
+            }
+
+            if !self.repos.iter().any(|r| r.is_same_repo(&repo)) {
+                self.repos.push(repo);
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct ConfigValidationErrors {
-    repo_errors: Vec<(usize, RepoValidationError)>,
+    config_errors: Vec<&'static str>,
+    repo_errors: Vec<(usize, Span, RepoValidationError)>,
+    org_errors: Vec<(usize, Span, OrgValidationError)>,
+    group_errors: Vec<(usize, Span, &'static str)>,
+    group_repo_errors: Vec<(usize, usize, Span, RepoValidationError)>,
+    source: Option<ConfigSource>,
+}
+
+type Span = Range<usize>;
+
+/// The file the config was read from; used to show where in it errors are
+#[derive(Debug)]
+struct ConfigSource {
+    path: Option<PathBuf>,
+    contents: String,
 }
 
 impl ConfigValidationErrors {
     pub fn new() -> Self {
         Self {
+            config_errors: Vec::new(),
             repo_errors: Vec::new(),
+            org_errors: Vec::new(),
+            group_errors: Vec::new(),
+            group_repo_errors: Vec::new(),
+            source: None,
         }
     }
 
-    fn add_repo_error(&mut self, version_index: usize, error: RepoValidationError) {
-        self.repo_errors.push((version_index, error));
+    fn add_config_error(&mut self, message: &'static str) {
+        self.config_errors.push(message);
+    }
+
+    fn add_repo_error(&mut self, version_index: usize, span: Span, error: RepoValidationError) {
+        self.repo_errors.push((version_index, span, error));
+    }
+
+    fn add_org_error(&mut self, org_index: usize, span: Span, error: OrgValidationError) {
+        self.org_errors.push((org_index, span, error));
+    }
+
+    fn add_group_error(&mut self, group_index: usize, span: Span, message: &'static str) {
+        self.group_errors.push((group_index, span, message));
+    }
+
+    fn add_group_repo_error(
+        &mut self,
+        group_index: usize,
+        repo_index: usize,
+        span: Span,
+        error: RepoValidationError,
+    ) {
+        self.group_repo_errors
+            .push((group_index, repo_index, span, error));
     }
 
     fn is_empty(&self) -> bool {
-        self.repo_errors.is_empty()
+        self.config_errors.is_empty()
+            && self.repo_errors.is_empty()
+            && self.org_errors.is_empty()
+            && self.group_errors.is_empty()
+            && self.group_repo_errors.is_empty()
+    }
+
+    fn with_source(mut self, path: Option<&Path>, contents: &str) -> Self {
+        self.source = Some(ConfigSource {
+            path: path.map(|p| p.to_path_buf()),
+            contents: contents.to_string(),
+        });
+        self
     }
 }
 
@@ -45,48 +177,329 @@ impl TryFrom<RawConfig> for Config {
     type Error = ConfigValidationErrors;
 
     fn try_from(value: RawConfig) -> Result<Self, Self::Error> {
-        let mut repos = vec![];
+        let mut entries = vec![];
         let mut errors = ConfigValidationErrors::new();
 
+        if let Some(url) = value.api_base_url.as_deref()
+            && parse_api_base_url(url).is_none()
+        {
+            errors.add_config_error(API_BASE_URL_ERROR);
+        }
+
+        let concurrency = value.concurrency.map(NonZeroUsize::new);
+        if concurrency.is_some_and(|c| c.is_none()) {
+            errors.add_config_error("concurrency needs to be greater than 0");
+        }
+
+        let request_timeout_secs = value.request_timeout_secs.map(NonZeroU64::new);
+        if request_timeout_secs.is_some_and(|t| t.is_none()) {
+            errors.add_config_error("request_timeout_secs needs to be greater than 0");
+        }
+
+        let connect_timeout_secs = value.connect_timeout_secs.map(NonZeroU64::new);
+        if connect_timeout_secs.is_some_and(|t| t.is_none()) {
+            errors.add_config_error("connect_timeout_secs needs to be greater than 0");
+        }
+
+        let deadline_secs = value.deadline_secs.map(NonZeroU64::new);
+        if deadline_secs.is_some_and(|d| d.is_none()) {
+            errors.add_config_error("deadline_secs needs to be greater than 0");
+        }
+
+        let defaults = value.defaults.unwrap_or_default();
+
         for (i, raw_repo) in value.repos.into_iter().enumerate() {
-            match Repo::try_from(raw_repo) {
-                Ok(r) => repos.push(r),
-                Err(e) => errors.add_repo_error(i, e),
+            let span = raw_repo.span();
+            let mut raw_repo = raw_repo.into_inner();
+            apply_api_base_url(&mut raw_repo, value.api_base_url.as_deref());
+            raw_repo.apply_defaults(&defaults);
+
+            match raw_repo.try_into_repos() {
+                Ok(repos) => entries.extend(repos.into_iter().map(|repo| RepoEntry {
+                    position: EntryPosition::TopLevel(i),
+                    span: span.clone(),
+                    repo,
+                })),
+                Err(e) => errors.add_repo_error(i, span, e),
+            }
+        }
+
+        let mut group_names = HashSet::new();
+        for (i, group) in value.groups.unwrap_or_default().into_iter().enumerate() {
+            let span = group.span();
+            let group = group.into_inner();
+            let name = group.name.trim().to_string();
+            if name.is_empty() {
+                errors.add_group_error(i, span, "group name is empty");
+            } else if !group_names.insert(name.clone()) {
+                errors.add_group_error(i, span, "group names need to be unique");
+            }
+
+            let group_defaults = group.defaults().or(&defaults);
+            for (j, raw_repo) in group.repos.into_iter().enumerate() {
+                let span = raw_repo.span();
+                let mut raw_repo = raw_repo.into_inner();
+                apply_api_base_url(&mut raw_repo, value.api_base_url.as_deref());
+                raw_repo.apply_defaults(&group_defaults);
+
+                match raw_repo.try_into_repos() {
+                    Ok(repos) => entries.extend(repos.into_iter().map(|mut repo| {
+                        repo.group = Some(name.clone());
+                        RepoEntry {
+                            position: EntryPosition::Grouped(i, j),
+                            span: span.clone(),
+                            repo,
+                        }
+                    })),
+                    Err(e) => errors.add_group_repo_error(i, j, span, e),
+                }
             }
         }
 
+        label_repeated_repos(&mut entries, &mut errors);
+
+        let mut orgs = vec![];
+        for (i, raw_org) in value.orgs.unwrap_or_default().into_iter().enumerate() {
+            let span = raw_org.span();
+            let mut raw_org = raw_org.into_inner();
+            if raw_org.api_base_url.is_none() {
+                raw_org.api_base_url = value.api_base_url.clone();
+            }
+
+            match Org::try_from(raw_org) {
+                Ok(mut o) => {
+                    o.repo_defaults = defaults.clone();
+                    orgs.push(o);
+                }
+                Err(e) => errors.add_org_error(i, span, e),
+            }
+        }
+
+        // errors for repeated repos are found after the others
+        errors.repo_errors.sort_by_key(|(i, _, _)| *i);
+        errors
+            .group_repo_errors
+            .sort_by_key(|(i, j, _, _)| (*i, *j));
+
         if errors.is_empty() {
-            Ok(Self { repos })
+            Ok(Self {
+                keep_going: value.keep_going.unwrap_or(false),
+                github_api: value.github_api,
+                concurrency: concurrency.flatten(),
+                request_timeout_secs: request_timeout_secs.flatten(),
+                connect_timeout_secs: connect_timeout_secs.flatten(),
+                deadline_secs: deadline_secs.flatten(),
+                repos: entries.into_iter().map(|entry| entry.repo).collect(),
+                orgs,
+            })
         } else {
             Err(errors)
         }
     }
 }
 
+/// A successfully validated repo, along with where it was listed in the config
+struct RepoEntry {
+    position: EntryPosition,
+    span: Span,
+    repo: Repo,
+}
+
+enum EntryPosition {
+    TopLevel(usize),
+    /// Group index, and the index of the repo within the group
+    Grouped(usize, usize),
+}
+
+// a repo can be listed more than once to track several head refs (eg. release branches); such
+// entries are labelled with their head ref unless they have a label already, so that they can be
+// told apart in the report
+fn label_repeated_repos(entries: &mut [RepoEntry], errors: &mut ConfigValidationErrors) {
+    let is_repeated: Vec<bool> = (0..entries.len())
+        .map(|i| {
+            entries
+                .iter()
+                .enumerate()
+                .any(|(j, other)| i != j && entries[i].repo.is_same_repo(&other.repo))
+        })
+        .collect();
+
+    for (entry, is_repeated) in entries.iter_mut().zip(&is_repeated) {
+        if *is_repeated && entry.repo.label.is_none() {
+            entry.repo.label = Some(entry.repo.head_ref.to_string());
+        }
+    }
+
+    // only the later of two conflicting entries is reported
+    for (i, entry) in entries.iter().enumerate() {
+        let earlier = entries[..i]
+            .iter()
+            .filter(|other| other.repo.is_same_repo(&entry.repo));
+
+        let mut error = RepoValidationError::new();
+        for other in earlier {
+            if other.repo.head_ref == entry.repo.head_ref {
+                error.add_error("repo is already listed with the same head_ref");
+                break;
+            }
+            if other.repo.label == entry.repo.label {
+                error.add_error("entries for the same repo need to have distinct labels");
+                break;
+            }
+        }
+
+        if error.is_empty() {
+            continue;
+        }
+
+        match entry.position {
+            EntryPosition::TopLevel(i) => errors.add_repo_error(i, entry.span.clone(), error),
+            EntryPosition::Grouped(i, j) => {
+                errors.add_group_repo_error(i, j, entry.span.clone(), error)
+            }
+        }
+    }
+}
+
+// the top level API base URL is only meant for GitHub
+fn apply_api_base_url(raw_repo: &mut RawRepo, api_base_url: Option<&str>) {
+    if raw_repo.api_base_url.is_none()
+        && raw_repo.host.is_none()
+        && raw_repo.path.is_none()
+        && raw_repo.forge.unwrap_or_default() == Forge::GitHub
+    {
+        raw_repo.api_base_url = api_base_url.map(|url| url.to_string());
+    }
+}
+
 impl std::fmt::Display for ConfigValidationErrors {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "config has errors:")?;
 
-        for (index, error) in &self.repo_errors {
-            writeln!(f, " - repo #{} has errors:", index)?;
+        for error in &self.config_errors {
+            writeln!(f, " - {}", error)?;
+        }
+
+        for (index, span, error) in &self.repo_errors {
+            writeln!(f, " - repo #{} has errors{}", index, self.location(span))?;
+            self.write_snippet(f, span)?;
             write!(f, "{}", error)?;
         }
 
+        for (index, span, error) in &self.org_errors {
+            writeln!(f, " - org #{} has errors{}", index, self.location(span))?;
+            self.write_snippet(f, span)?;
+            write!(f, "{}", error)?;
+        }
+
+        for (index, span, error) in &self.group_errors {
+            writeln!(f, " - group #{} has errors{}", index, self.location(span))?;
+            self.write_snippet(f, span)?;
+            writeln!(f, "   - {}", error)?;
+        }
+
+        for (group_index, repo_index, span, error) in &self.group_repo_errors {
+            writeln!(
+                f,
+                " - group #{} repo #{} has errors{}",
+                group_index,
+                repo_index,
+                self.location(span)
+            )?;
+            self.write_snippet(f, span)?;
+            write!(f, "{}", error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigValidationErrors {
+    // eg. " (unreleased.toml, line 4, column 1):"
+    fn location(&self, span: &Span) -> String {
+        let Some(source) = &self.source else {
+            return ":".to_string();
+        };
+
+        let (line, column) = get_line_and_column(&source.contents, span.start);
+        match &source.path {
+            Some(path) => format!(
+                " ({}, line {}, column {}):",
+                path.to_string_lossy(),
+                line,
+                column
+            ),
+            None => format!(" (line {}, column {}):", line, column),
+        }
+    }
+
+    // follows the format the toml crate uses for parse errors; only the first line of the span is
+    // shown, which for a table is its header
+    fn write_snippet(&self, f: &mut std::fmt::Formatter<'_>, span: &Span) -> std::fmt::Result {
+        let Some(source) = &self.source else {
+            return Ok(());
+        };
+
+        let (line, _) = get_line_and_column(&source.contents, span.start);
+        let line_start = get_line_start(&source.contents, span.start);
+        let line_contents = source.contents[line_start..]
+            .lines()
+            .next()
+            .unwrap_or_default();
+        let span_end = span.end.min(line_start + line_contents.len());
+        let caret_count = source.contents[span.start..span_end].chars().count().max(1);
+
+        let gutter = " ".repeat(line.to_string().len());
+        writeln!(f, "   {} |", gutter)?;
+        writeln!(f, "   {} | {}", line, line_contents)?;
+        writeln!(
+            f,
+            "   {} | {}{}",
+            gutter,
+            " ".repeat(source.contents[line_start..span.start].chars().count()),
+            "^".repeat(caret_count)
+        )?;
+
         Ok(())
     }
 }
 
-pub fn parse_config<S>(contents: S, repo_filter: Option<&Regex>) -> anyhow::Result<Config>
-where
-    S: AsRef<str>,
-{
-    let mut raw: RawConfig =
-        toml::from_str(contents.as_ref()).context("couldn't deserialize TOML")?;
+// both are 1-based; the column counts characters (not bytes)
+fn get_line_and_column(contents: &str, offset: usize) -> (usize, usize) {
+    let line = contents[..offset].matches('\n').count() + 1;
+    let line_start = get_line_start(contents, offset);
+
+    (line, contents[line_start..offset].chars().count() + 1)
+}
+
+// the byte offset of the start of the line the offset is on
+fn get_line_start(contents: &str, offset: usize) -> usize {
+    contents[..offset].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// Parses and validates the config; if provided, the path of the file the config was read from is
+/// included in validation errors
+pub fn parse_config(
+    contents: &str,
+    path: Option<&Path>,
+    repo_filter: Option<&Regex>,
+) -> anyhow::Result<Config> {
+    let mut raw: RawConfig = toml::from_str(contents).context("couldn't deserialize TOML")?;
 
+    // repos in groups are selected either by their own name, or by the group's name
     if let Some(regex) = repo_filter {
-        raw.repos.retain(|v| regex.is_match(&v.repo));
+        raw.repos
+            .retain(|v| v.get_ref().name().is_some_and(|name| regex.is_match(name)));
+        for group in raw.groups.iter_mut().flatten() {
+            let group = group.get_mut();
+            if !regex.is_match(&group.name) {
+                group
+                    .repos
+                    .retain(|v| v.get_ref().name().is_some_and(|name| regex.is_match(name)));
+            }
+        }
     }
-    let config: Config = raw.try_into()?;
+    let config = Config::try_from(raw).map_err(|errors| errors.with_source(path, contents))?;
 
     Ok(config)
 }
@@ -103,21 +516,104 @@ pub struct HtmlConfig {
     pub template: Option<String>,
 }
 
+#[derive(Debug, Clone)]
+pub struct MarkdownConfig {
+    pub template: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TemplateConfig {
+    pub template: String,
+    pub output_path: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone)]
 pub enum OutputType {
     Stdout(StdoutConfig),
     Html(HtmlConfig),
+    Json,
+    Markdown(MarkdownConfig),
+    Template(TemplateConfig),
+}
+
+impl OutputType {
+    pub fn format(&self) -> OutputFormat {
+        match self {
+            OutputType::Stdout(_) => OutputFormat::Stdout,
+            OutputType::Html(_) => OutputFormat::Html,
+            OutputType::Json => OutputFormat::Json,
+            OutputType::Markdown(_) => OutputFormat::Markdown,
+            OutputType::Template(_) => OutputFormat::Template,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FetchConfig {
+    /// How many of the most recent commits to show for repos that have never been released
+    pub never_released_max_commits: usize,
+    /// How far back to look for commits for repos that have never been released
+    pub never_released_max_age_days: Option<u32>,
+    pub github_api: GitHubApi,
+    /// How many repos (or batches of repos) are fetched at the same time
+    pub concurrency: usize,
+    /// When to stop waiting for changelogs; the ones not fetched by then are reported as errors
+    pub deadline: Option<Instant>,
+}
+
+/// Timeouts applied to every request made to forges
+#[derive(Debug, Clone, Copy)]
+pub struct RequestTimeouts {
+    /// How long to wait for a response, from when the request is sent
+    pub request: Duration,
+    pub connect: Duration,
+}
+
+/// The API used to fetch changelogs for GitHub repos
+#[derive(Debug, Clone, Copy, Default, Deserialize, Eq, PartialEq, ValueEnum)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(rename_all = "lowercase")]
+pub enum GitHubApi {
+    // GraphQL for hosts with enough repos for batching to pay off, REST otherwise; variants aren't
+    // doc commented, since clap would switch to its long help format to show them
+    #[default]
+    Auto,
+    // one request per lookup, per repo
+    Rest,
+    // lookups for many repos batched into a few queries
+    Graphql,
+}
+
+impl Display for GitHubApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let api = match self {
+            GitHubApi::Auto => "auto",
+            GitHubApi::Rest => "rest",
+            GitHubApi::Graphql => "graphql",
+        };
+
+        write!(f, "{}", api)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct RunConfig {
     pub output_type: OutputType,
+    pub repo_filter: Option<String>,
+    pub keep_going: bool,
+    pub fetch_config: FetchConfig,
+    pub timeouts: RequestTimeouts,
+    /// The deadline as configured; fetch_config only has the point in time it works out to
+    pub deadline_secs: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum OutputFormat {
     Stdout,
     Html,
+    Json,
+    Markdown,
+    Template,
 }
 
 impl Display for OutputFormat {
@@ -125,6 +621,9 @@ impl Display for OutputFormat {
         let format = match self {
             OutputFormat::Stdout => "stdout",
             OutputFormat::Html => "html",
+            OutputFormat::Json => "json",
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Template => "template",
         };
 
         write!(f, "{}", format)
@@ -156,127 +655,1282 @@ repo = "owner/repo-c"
 consider_prereleases = true
 "#;
         // WHEN
-        let result = parse_config(config_str, None)?;
+        let result = parse_config(config_str, None, None)?;
 
         // THEN
-        assert_yaml_snapshot!(result, @r"
+        assert_yaml_snapshot!(result, @r#"
+        keep_going: false
+        github_api: ~
+        concurrency: ~
+        request_timeout_secs: ~
+        connect_timeout_secs: ~
+        deadline_secs: ~
         repos:
           - owner: owner
             repo: repo-a
-            head_ref: main
+            forge: github
+            head_ref:
+              DefaultBranch: ~
             consider_prereleases: false
+            api_base_url: "https://api.github.com"
+            baseline: release
+            tag_pattern: ~
+            packages: []
+            group: ~
+            label: ~
           - owner: owner
             repo: repo-b
-            head_ref: branch
+            forge: github
+            head_ref:
+              Configured: branch
             consider_prereleases: false
+            api_base_url: "https://api.github.com"
+            baseline: release
+            tag_pattern: ~
+            packages: []
+            group: ~
+            label: ~
           - owner: owner
             repo: repo-c
-            head_ref: main
+            forge: github
+            head_ref:
+              DefaultBranch: ~
             consider_prereleases: true
-        ");
+            api_base_url: "https://api.github.com"
+            baseline: release
+            tag_pattern: ~
+            packages: []
+            group: ~
+            label: ~
+        orgs: []
+        "#);
 
         Ok(())
     }
 
-    //------------//
-    //  FAILURES  //
-    //------------//
-
     #[test]
-    fn parsing_invalid_toml_fails() {
+    fn api_base_url_can_be_overridden_globally_and_per_repo() -> anyhow::Result<()> {
         // GIVEN
         let config_str = r#"
+api_base_url = "https://github.example.com/api/v3/"
+
 [[repos]]
-repo   "owner/repo-a"
+repo = "owner/repo-a"
+
+[[repos]]
+repo = "owner/repo-b"
+api_base_url = "https://api.github.com"
 "#;
         // WHEN
-        let result = parse_config(config_str, None).expect_err("result should've been an error");
+        let result = parse_config(config_str, None, None)?;
 
         // THEN
-        assert_debug_snapshot!(result, @r#"
-        Error {
-            context: "couldn\'t deserialize TOML",
-            source: Error {
-                message: "key with no value, expected `=`",
-                input: Some(
-                    "\n[[repos]]\nrepo   \"owner/repo-a\"\n",
-                ),
-                keys: [],
-                span: Some(
-                    18..18,
-                ),
-            },
-        }
+        assert_yaml_snapshot!(result, @r#"
+        keep_going: false
+        github_api: ~
+        concurrency: ~
+        request_timeout_secs: ~
+        connect_timeout_secs: ~
+        deadline_secs: ~
+        repos:
+          - owner: owner
+            repo: repo-a
+            forge: github
+            head_ref:
+              DefaultBranch: ~
+            consider_prereleases: false
+            api_base_url: "https://github.example.com/api/v3"
+            baseline: release
+            tag_pattern: ~
+            packages: []
+            group: ~
+            label: ~
+          - owner: owner
+            repo: repo-b
+            forge: github
+            head_ref:
+              DefaultBranch: ~
+            consider_prereleases: false
+            api_base_url: "https://api.github.com"
+            baseline: release
+            tag_pattern: ~
+            packages: []
+            group: ~
+            label: ~
+        orgs: []
         "#);
+
+        Ok(())
     }
 
     #[test]
-    fn parsing_config_with_invalid_schema_fails() {
+    fn parsing_config_with_tag_baseline_works() -> anyhow::Result<()> {
         // GIVEN
         let config_str = r#"
 [[repos]]
 repo = "owner/repo-a"
-consider_prereleases = "yes"
+baseline = "tag"
+
+[[repos]]
+repo = "owner/repo-b"
+baseline = "tag"
+tag_pattern = '^v1\.'
 "#;
         // WHEN
-        let result = parse_config(config_str, None).expect_err("result should've been an error");
+        let result = parse_config(config_str, None, None)?;
 
         // THEN
-        assert_debug_snapshot!(result, @r#"
-        Error {
-            context: "couldn\'t deserialize TOML",
-            source: Error {
-                message: "invalid type: string \"yes\", expected a boolean",
-                input: Some(
-                    "\n[[repos]]\nrepo = \"owner/repo-a\"\nconsider_prereleases = \"yes\"\n",
-                ),
-                keys: [
-                    "repos",
-                    "consider_prereleases",
-                ],
-                span: Some(
-                    56..61,
-                ),
-            },
-        }
+        assert_yaml_snapshot!(result, @r#"
+        keep_going: false
+        github_api: ~
+        concurrency: ~
+        request_timeout_secs: ~
+        connect_timeout_secs: ~
+        deadline_secs: ~
+        repos:
+          - owner: owner
+            repo: repo-a
+            forge: github
+            head_ref:
+              DefaultBranch: ~
+            consider_prereleases: false
+            api_base_url: "https://api.github.com"
+            baseline: tag
+            tag_pattern: ~
+            packages: []
+            group: ~
+            label: ~
+          - owner: owner
+            repo: repo-b
+            forge: github
+            head_ref:
+              DefaultBranch: ~
+            consider_prereleases: false
+            api_base_url: "https://api.github.com"
+            baseline: tag
+            tag_pattern: "^v1\\."
+            packages: []
+            group: ~
+            label: ~
+        orgs: []
         "#);
+
+        Ok(())
     }
 
     #[test]
-    fn parsing_config_with_invalid_data_fails() {
+    fn parsing_config_with_packages_works() -> anyhow::Result<()> {
         // GIVEN
         let config_str = r#"
 [[repos]]
-repo = "owner/repo-a"
+repo = "owner/monorepo"
 
-[[repos]]
-repo = "ownerrepo-b"
+[[repos.packages]]
+name = "api"
+tag_prefix = "api/"
+paths = ["services/api/", "libs/common"]
+
+[[repos.packages]]
+name = "web"
+tag_prefix = "web/"
+"#;
+        // WHEN
+        let result = parse_config(config_str, None, None)?;
+
+        // THEN
+        assert_yaml_snapshot!(result, @r#"
+        keep_going: false
+        github_api: ~
+        concurrency: ~
+        request_timeout_secs: ~
+        connect_timeout_secs: ~
+        deadline_secs: ~
+        repos:
+          - owner: owner
+            repo: monorepo
+            forge: github
+            head_ref:
+              DefaultBranch: ~
+            consider_prereleases: false
+            api_base_url: "https://api.github.com"
+            baseline: tag
+            tag_pattern: ~
+            packages:
+              - name: api
+                tag_prefix: api/
+                paths:
+                  - services/api
+                  - libs/common
+              - name: web
+                tag_prefix: web/
+                paths: []
+            group: ~
+            label: ~
+        orgs: []
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parsing_config_with_gitlab_repos_works() -> anyhow::Result<()> {
+        // GIVEN
+        let config_str = r#"
+api_base_url = "https://github.example.com/api/v3"
 
 [[repos]]
-repo = "owner/"
+repo = "owner/repo-a"
+forge = "gitlab"
 
 [[repos]]
-repo = "/repo"
+repo = "group/subgroup/repo-b"
+forge = "gitlab"
+host = "gitlab.example.com"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str, None, None)?;
+
+        // THEN
+        assert_yaml_snapshot!(result, @r#"
+        keep_going: false
+        github_api: ~
+        concurrency: ~
+        request_timeout_secs: ~
+        connect_timeout_secs: ~
+        deadline_secs: ~
+        repos:
+          - owner: owner
+            repo: repo-a
+            forge: gitlab
+            head_ref:
+              DefaultBranch: ~
+            consider_prereleases: false
+            api_base_url: "https://gitlab.com/api/v4"
+            baseline: release
+            tag_pattern: ~
+            packages: []
+            group: ~
+            label: ~
+          - owner: group
+            repo: subgroup/repo-b
+            forge: gitlab
+            head_ref:
+              DefaultBranch: ~
+            consider_prereleases: false
+            api_base_url: "https://gitlab.example.com/api/v4"
+            baseline: release
+            tag_pattern: ~
+            packages: []
+            group: ~
+            label: ~
+        orgs: []
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parsing_config_with_local_repos_works() -> anyhow::Result<()> {
+        // GIVEN
+        let config_str = r#"
+api_base_url = "https://github.example.com/api/v3"
 
 [[repos]]
-repo = ""
-head_ref = ""
+path = "/src/repo-a"
+head_ref = "develop"
+tag_pattern = '^v1\.'
 "#;
+
         // WHEN
-        let result = parse_config(config_str, None).expect_err("result should've been an error");
+        let result = parse_config(config_str, None, None)?;
 
         // THEN
-        assert_snapshot!(result, @r#"
-        config has errors:
-         - repo #1 has errors:
-           - repo needs to be in the format "owner/repo"
-         - repo #2 has errors:
-           - repo name is empty
-         - repo #3 has errors:
-           - owner is empty
-         - repo #4 has errors:
-           - repo needs to be in the format "owner/repo"
-           - head_ref is empty
+        assert_yaml_snapshot!(result, @r#"
+        keep_going: false
+        github_api: ~
+        concurrency: ~
+        request_timeout_secs: ~
+        connect_timeout_secs: ~
+        deadline_secs: ~
+        repos:
+          - owner: ""
+            repo: /src/repo-a
+            forge: local
+            head_ref:
+              Configured: develop
+            consider_prereleases: false
+            api_base_url: ""
+            baseline: tag
+            tag_pattern: "^v1\\."
+            packages: []
+            group: ~
+            label: ~
+        orgs: []
         "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parsing_config_with_orgs_works() -> anyhow::Result<()> {
+        // GIVEN
+        let config_str = r#"
+api_base_url = "https://github.example.com/api/v3"
+
+[[orgs]]
+name = "owner"
+
+[[orgs]]
+name = "user"
+kind = "user"
+api_base_url = "https://api.github.com"
+topics = ["service"]
+visibility = "private"
+include_archived = true
+include_forks = true
+name_pattern = "^svc-"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str, None, None)?;
+
+        // THEN
+        assert_yaml_snapshot!(result, @r#"
+        keep_going: false
+        github_api: ~
+        concurrency: ~
+        request_timeout_secs: ~
+        connect_timeout_secs: ~
+        deadline_secs: ~
+        repos: []
+        orgs:
+          - name: owner
+            kind: org
+            api_base_url: "https://github.example.com/api/v3"
+            topics: []
+            visibility: ~
+            include_archived: false
+            include_forks: false
+            name_pattern: ~
+            repo_defaults:
+              head_ref: ~
+              consider_prereleases: ~
+              baseline: ~
+          - name: user
+            kind: user
+            api_base_url: "https://api.github.com"
+            topics:
+              - service
+            visibility: private
+            include_archived: true
+            include_forks: true
+            name_pattern: ^svc-
+            repo_defaults:
+              head_ref: ~
+              consider_prereleases: ~
+              baseline: ~
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn discovered_repos_are_added_unless_already_present() -> anyhow::Result<()> {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "develop"
+
+[[orgs]]
+name = "owner"
+"#;
+        let mut config = parse_config(config_str, None, None)?;
+        let discovered = [
+            "owner/repo-a",
+            "owner/repo-b",
+            "owner/other",
+            "owner/repo-b",
+        ]
+        .into_iter()
+        .map(|name| {
+            Repo::try_from(RawRepo {
+                repo: Some(name.to_string()),
+                path: None,
+                forge: None,
+                host: None,
+                head_ref: None,
+                head_refs: None,
+                consider_prereleases: None,
+                api_base_url: None,
+                baseline: None,
+                tag_pattern: None,
+                packages: None,
+                label: None,
+            })
+            .expect("repo should've been valid")
+        })
+        .collect();
+        let filter = Regex::new("repo-")?;
+
+        // WHEN
+        config.add_discovered_repos(discovered, Some(&filter));
+
+        // THEN
+        let repos = config
+            .repos
+            .iter()
+            .map(|r| format!("{} ({})", r, r.head_ref))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            repos,
+            vec!["owner/repo-a (develop)", "owner/repo-b (default branch)"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parsing_config_with_defaults_and_groups_works() -> anyhow::Result<()> {
+        // GIVEN
+        let config_str = r#"
+[defaults]
+head_ref = "master"
+consider_prereleases = true
+
+[[repos]]
+repo = "owner/repo-a"
+
+[[repos]]
+repo = "owner/repo-b"
+head_ref = "main"
+consider_prereleases = false
+
+[[groups]]
+name = "payments"
+head_ref = "develop"
+baseline = "tag"
+
+[[groups.repos]]
+repo = "owner/repo-c"
+
+[[groups.repos]]
+repo = "owner/repo-d"
+baseline = "release"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str, None, None)?;
+
+        // THEN
+        let repos = result
+            .repos
+            .iter()
+            .map(|r| {
+                format!(
+                    "{} group={:?} head_ref={} consider_prereleases={} baseline={:?}",
+                    r, r.group, r.head_ref, r.consider_prereleases, r.baseline
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(repos, @r#"
+        - owner/repo-a group=None head_ref=master consider_prereleases=true baseline=Release
+        - owner/repo-b group=None head_ref=main consider_prereleases=false baseline=Release
+        - "owner/repo-c group=Some(\"payments\") head_ref=develop consider_prereleases=true baseline=Tag"
+        - "owner/repo-d group=Some(\"payments\") head_ref=develop consider_prereleases=true baseline=Release"
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn repos_can_be_filtered_by_group_name() -> anyhow::Result<()> {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/payments-api"
+
+[[groups]]
+name = "payments"
+
+[[groups.repos]]
+repo = "owner/billing"
+
+[[groups]]
+name = "platform"
+
+[[groups.repos]]
+repo = "owner/gateway"
+
+[[groups.repos]]
+repo = "owner/payments-gateway"
+"#;
+        let filter = Regex::new("payments")?;
+
+        // WHEN
+        let result = parse_config(config_str, None, Some(&filter))?;
+
+        // THEN
+        let repos = result
+            .repos
+            .iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            repos,
+            vec![
+                "owner/payments-api",
+                "owner/billing",
+                "owner/payments-gateway"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn repos_with_several_head_refs_are_expanded() -> anyhow::Result<()> {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+head_refs = [
+    "main",
+    { ref = "release/1.x", tag_pattern = '^v1\.' },
+    { ref = "release/2.x", tag_pattern = '^v2\.' },
+]
+
+[[repos]]
+repo = "owner/repo-b"
+head_refs = ["develop"]
+"#;
+
+        // WHEN
+        let result = parse_config(config_str, None, None)?;
+
+        // THEN
+        let repos = result
+            .repos
+            .iter()
+            .map(|r| {
+                format!(
+                    "{} head_ref={} label={:?} baseline={:?} tag_pattern={}",
+                    r,
+                    r.head_ref,
+                    r.label,
+                    r.baseline,
+                    r.tag_pattern.is_some()
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(repos, @r#"
+        - "owner/repo-a head_ref=main label=Some(\"main\") baseline=Release tag_pattern=false"
+        - "owner/repo-a head_ref=release/1.x label=Some(\"release/1.x\") baseline=Tag tag_pattern=true"
+        - "owner/repo-a head_ref=release/2.x label=Some(\"release/2.x\") baseline=Tag tag_pattern=true"
+        - owner/repo-b head_ref=develop label=None baseline=Release tag_pattern=false
+        "#);
+        assert!(
+            result.repos[1]
+                .tag_pattern
+                .as_ref()
+                .is_some_and(|p| p.is_match("v1.4.2"))
+        );
+        assert!(
+            result.repos[1]
+                .tag_pattern
+                .as_ref()
+                .is_some_and(|p| !p.is_match("v2.0.0"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn repos_listed_with_different_head_refs_are_labelled() -> anyhow::Result<()> {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+
+[[repos]]
+repo = "Owner/Repo-A"
+head_ref = "release/1.x"
+
+[[repos]]
+repo = "owner/repo-b"
+label = "backend"
+
+[[groups]]
+name = "releases"
+
+[[groups.repos]]
+repo = "owner/repo-a"
+head_ref = "release/2.x"
+label = "v2"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str, None, None)?;
+
+        // THEN
+        let repos = result
+            .repos
+            .iter()
+            .map(|r| format!("{} head_ref={} label={:?}", r, r.head_ref, r.label))
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(repos, @r#"
+        - "owner/repo-a head_ref=default branch label=Some(\"default branch\")"
+        - "Owner/Repo-A head_ref=release/1.x label=Some(\"release/1.x\")"
+        - "owner/repo-b head_ref=default branch label=Some(\"backend\")"
+        - "owner/repo-a head_ref=release/2.x label=Some(\"v2\")"
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn github_api_can_be_chosen_in_config() -> anyhow::Result<()> {
+        // GIVEN
+        let config_str = r#"
+github_api = "graphql"
+
+[[repos]]
+repo = "owner/repo-a"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str, None, None)?;
+
+        // THEN
+        assert_eq!(result.github_api, Some(GitHubApi::Graphql));
+
+        Ok(())
+    }
+
+    //------------//
+    //  FAILURES  //
+    //------------//
+
+    #[test]
+    fn parsing_invalid_toml_fails() {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo   "owner/repo-a"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_debug_snapshot!(result, @r#"
+        Error {
+            context: "couldn\'t deserialize TOML",
+            source: Error {
+                message: "key with no value, expected `=`",
+                input: Some(
+                    "\n[[repos]]\nrepo   \"owner/repo-a\"\n",
+                ),
+                keys: [],
+                span: Some(
+                    18..18,
+                ),
+            },
+        }
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_schema_fails() {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+consider_prereleases = "yes"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_debug_snapshot!(result, @r#"
+        Error {
+            context: "couldn\'t deserialize TOML",
+            source: Error {
+                message: "invalid type: string \"yes\", expected a boolean",
+                input: Some(
+                    "\n[[repos]]\nrepo = \"owner/repo-a\"\nconsider_prereleases = \"yes\"\n",
+                ),
+                keys: [
+                    "repos",
+                    "consider_prereleases",
+                ],
+                span: Some(
+                    56..61,
+                ),
+            },
+        }
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_data_fails() {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+
+[[repos]]
+repo = "ownerrepo-b"
+
+[[repos]]
+repo = "owner/"
+
+[[repos]]
+repo = "/repo"
+
+[[repos]]
+repo = ""
+head_ref = ""
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        config has errors:
+         - repo #1 has errors (line 5, column 1):
+             |
+           5 | [[repos]]
+             | ^^^^^^^^^
+           - repo needs to be in the format "owner/repo"
+         - repo #2 has errors (line 8, column 1):
+             |
+           8 | [[repos]]
+             | ^^^^^^^^^
+           - repo name is empty
+         - repo #3 has errors (line 11, column 1):
+              |
+           11 | [[repos]]
+              | ^^^^^^^^^
+           - owner is empty
+         - repo #4 has errors (line 14, column 1):
+              |
+           14 | [[repos]]
+              | ^^^^^^^^^
+           - repo needs to be in the format "owner/repo"
+           - head_ref is empty
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_fetch_settings_fails() {
+        // GIVEN
+        let config_str = r#"
+concurrency = 0
+request_timeout_secs = 0
+connect_timeout_secs = 0
+deadline_secs = 0
+
+[[repos]]
+repo = "owner/repo-a"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r"
+        config has errors:
+         - concurrency needs to be greater than 0
+         - request_timeout_secs needs to be greater than 0
+         - connect_timeout_secs needs to be greater than 0
+         - deadline_secs needs to be greater than 0
+        ");
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_api_base_urls_fails() {
+        // GIVEN
+        let config_str = r#"
+api_base_url = "github.example.com"
+
+[[repos]]
+repo = "owner/repo-a"
+api_base_url = "https://"
+
+[[repos]]
+repo = "owner/repo-b"
+api_base_url = "https://api.github.com"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        config has errors:
+         - api_base_url needs to be an HTTP(S) URL, eg. "https://github.example.com/api/v3"
+         - repo #0 has errors (line 4, column 1):
+             |
+           4 | [[repos]]
+             | ^^^^^^^^^
+           - api_base_url needs to be an HTTP(S) URL, eg. "https://github.example.com/api/v3"
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_tag_settings_fails() {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+tag_pattern = "^v1"
+
+[[repos]]
+repo = "owner/repo-b"
+baseline = "tag"
+tag_pattern = "(v1"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        config has errors:
+         - repo #0 has errors (line 2, column 1):
+             |
+           2 | [[repos]]
+             | ^^^^^^^^^
+           - tag_pattern can only be used when baseline is "tag"
+         - repo #1 has errors (line 6, column 1):
+             |
+           6 | [[repos]]
+             | ^^^^^^^^^
+           - tag_pattern is not a valid regex
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_packages_fails() {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/monorepo-a"
+baseline = "release"
+
+[[repos.packages]]
+name = "api"
+tag_prefix = "api/"
+
+[[repos]]
+repo = "owner/monorepo-b"
+
+[[repos.packages]]
+name = "api"
+tag_prefix = ""
+paths = [""]
+
+[[repos.packages]]
+name = "api"
+tag_prefix = "api/"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        config has errors:
+         - repo #0 has errors (line 2, column 1):
+             |
+           2 | [[repos]]
+             | ^^^^^^^^^
+           - packages can only be used when baseline is "tag"
+         - repo #1 has errors (line 10, column 1):
+              |
+           10 | [[repos]]
+              | ^^^^^^^^^
+           - package tag_prefix is empty
+           - package paths cannot be empty
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_hosts_fails() {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+forge = "gitlab"
+host = "gitlab.example.com"
+api_base_url = "https://gitlab.example.com/api/v4"
+
+[[repos]]
+repo = "owner/repo-b"
+forge = "gitlab"
+host = "https://"
+
+[[repos]]
+repo = "owner/repo-c"
+forge = "forgejo"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        config has errors:
+         - repo #0 has errors (line 2, column 1):
+             |
+           2 | [[repos]]
+             | ^^^^^^^^^
+           - only one of api_base_url and host can be set
+         - repo #1 has errors (line 8, column 1):
+             |
+           8 | [[repos]]
+             | ^^^^^^^^^
+           - host needs to be a hostname, eg. "git.example.com"
+         - repo #2 has errors (line 13, column 1):
+              |
+           13 | [[repos]]
+              | ^^^^^^^^^
+           - host needs to be set for Gitea/Forgejo repos
+        "#);
+    }
+
+    #[test]
+    fn error_columns_count_characters_rather_than_bytes() {
+        // GIVEN
+        let config_str = r#"
+repos = [{ repo = "owner/repo-a", label = "café ☕" }, { repo = "owner/repo-b", forge = "forgejo" }]
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        config has errors:
+         - repo #1 has errors (line 2, column 55):
+             |
+           2 | repos = [{ repo = "owner/repo-a", label = "café ☕" }, { repo = "owner/repo-b", forge = "forgejo" }]
+             |                                                       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+           - host needs to be set for Gitea/Forgejo repos
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_local_repos_fails() {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+path = "/src/repo-a"
+
+[[repos]]
+head_ref = "main"
+
+[[repos]]
+path = " "
+
+[[repos]]
+path = "/src/repo-d"
+forge = "gitlab"
+baseline = "release"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        config has errors:
+         - repo #0 has errors (line 2, column 1):
+             |
+           2 | [[repos]]
+             | ^^^^^^^^^
+           - only one of repo and path can be set
+         - repo #1 has errors (line 6, column 1):
+             |
+           6 | [[repos]]
+             | ^^^^^^^^^
+           - one of repo and path needs to be set
+         - repo #2 has errors (line 9, column 1):
+             |
+           9 | [[repos]]
+             | ^^^^^^^^^
+           - path is empty
+         - repo #3 has errors (line 12, column 1):
+              |
+           12 | [[repos]]
+              | ^^^^^^^^^
+           - forge, host, and api_base_url can't be set for local repos
+           - baseline needs to be "tag" for local repos
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_orgs_fails() {
+        // GIVEN
+        let config_str = r#"
+[[orgs]]
+name = " "
+
+[[orgs]]
+name = "owner/repo"
+api_base_url = "github.example.com"
+topics = [""]
+name_pattern = "(svc"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        config has errors:
+         - org #0 has errors (line 2, column 1):
+             |
+           2 | [[orgs]]
+             | ^^^^^^^^
+           - org name is empty
+         - org #1 has errors (line 5, column 1):
+             |
+           5 | [[orgs]]
+             | ^^^^^^^^
+           - org name cannot contain "/"
+           - api_base_url needs to be an HTTP(S) URL, eg. "https://github.example.com/api/v3"
+           - topics cannot be empty
+           - name_pattern is not a valid regex
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_groups_fails() {
+        // GIVEN
+        let config_str = r#"
+[[groups]]
+name = "payments"
+
+[[groups]]
+name = " "
+
+[[groups.repos]]
+repo = "owner/"
+
+[[groups]]
+name = "payments"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r"
+        config has errors:
+         - group #1 has errors (line 5, column 1):
+             |
+           5 | [[groups]]
+             | ^^^^^^^^^^
+           - group name is empty
+         - group #2 has errors (line 11, column 1):
+              |
+           11 | [[groups]]
+              | ^^^^^^^^^^
+           - group names need to be unique
+         - group #1 repo #0 has errors (line 8, column 1):
+             |
+           8 | [[groups.repos]]
+             | ^^^^^^^^^^^^^^^^
+           - repo name is empty
+        ");
+    }
+
+    #[test]
+    fn parsing_config_with_unknown_keys_fails() {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+head-ref = "develop"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(format!("{:#}", result), @r#"
+        couldn't deserialize TOML: TOML parse error at line 4, column 1
+          |
+        4 | head-ref = "develop"
+          | ^^^^^^^^
+        unknown field `head-ref`, expected one of `repo`, `path`, `forge`, `host`, `head_ref`, `head_refs`, `consider_prereleases`, `api_base_url`, `baseline`, `tag_pattern`, `packages`, `label`
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_unknown_group_keys_fails() {
+        // GIVEN
+        let config_str = r#"
+[[groups]]
+name = "payments"
+head_ref = "develop"
+consider_pre_releases = true
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(format!("{:#}", result), @r"
+        couldn't deserialize TOML: TOML parse error at line 5, column 1
+          |
+        5 | consider_pre_releases = true
+          | ^^^^^^^^^^^^^^^^^^^^^
+        unknown field `consider_pre_releases`, expected one of `name`, `head_ref`, `consider_prereleases`, `baseline`, `repos`
+        ");
+    }
+
+    #[test]
+    fn errors_for_inline_repos_point_at_their_tables() {
+        // GIVEN
+        let config_str = r#"
+repos = [{ repo = "owner/repo-a" }, { repo = "owner/" }]
+"#;
+        // WHEN
+        let result = parse_config(config_str, Some(Path::new("unreleased.toml")), None)
+            .expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r#"
+        config has errors:
+         - repo #1 has errors (unreleased.toml, line 2, column 37):
+             |
+           2 | repos = [{ repo = "owner/repo-a" }, { repo = "owner/" }]
+             |                                     ^^^^^^^^^^^^^^^^^^^
+           - repo name is empty
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_repeated_repos_fails() {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+
+[[repos]]
+repo = "OWNER/repo-a"
+
+[[repos]]
+repo = "owner/repo-b"
+head_ref = "main"
+label = "stable"
+
+[[repos]]
+repo = "owner/repo-b"
+head_ref = "develop"
+label = "stable"
+
+[[repos]]
+repo = "owner/repo-c"
+label = " "
+
+[[groups]]
+name = "payments"
+
+[[groups.repos]]
+repo = "owner/repo-a"
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r"
+        config has errors:
+         - repo #1 has errors (line 5, column 1):
+             |
+           5 | [[repos]]
+             | ^^^^^^^^^
+           - repo is already listed with the same head_ref
+         - repo #3 has errors (line 13, column 1):
+              |
+           13 | [[repos]]
+              | ^^^^^^^^^
+           - entries for the same repo need to have distinct labels
+         - repo #4 has errors (line 18, column 1):
+              |
+           18 | [[repos]]
+              | ^^^^^^^^^
+           - label is empty
+         - group #0 repo #0 has errors (line 25, column 1):
+              |
+           25 | [[groups.repos]]
+              | ^^^^^^^^^^^^^^^^
+           - repo is already listed with the same head_ref
+        ");
+    }
+
+    #[test]
+    fn parsing_config_with_invalid_head_refs_fails() {
+        // GIVEN
+        let config_str = r#"
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+head_refs = ["develop"]
+
+[[repos]]
+repo = "owner/repo-b"
+head_refs = []
+
+[[repos]]
+repo = "owner/repo-c"
+label = "c"
+head_refs = ["main"]
+
+[[repos]]
+repo = "owner/repo-d"
+baseline = "release"
+head_refs = [
+    { ref = " ", tag_pattern = "[" },
+    { ref = "release/1.x", tag_pattern = "[" },
+]
+"#;
+        // WHEN
+        let result =
+            parse_config(config_str, None, None).expect_err("result should've been an error");
+
+        // THEN
+        assert_snapshot!(result, @r"
+        config has errors:
+         - repo #0 has errors (line 2, column 1):
+             |
+           2 | [[repos]]
+             | ^^^^^^^^^
+           - only one of head_ref and head_refs can be set
+         - repo #1 has errors (line 7, column 1):
+             |
+           7 | [[repos]]
+             | ^^^^^^^^^
+           - head_refs is empty
+         - repo #2 has errors (line 11, column 1):
+              |
+           11 | [[repos]]
+              | ^^^^^^^^^
+           - label can't be set along with head_refs
+         - repo #3 has errors (line 16, column 1):
+              |
+           16 | [[repos]]
+              | ^^^^^^^^^
+           - head_ref is empty
+           - tag_pattern is not a valid regex
+        ");
     }
 }
@@ -0,0 +1,144 @@
+use super::repo::{DEFAULT_API_BASE_URL, DEFAULT_GITLAB_API_BASE_URL, get_host_from_api_base_url};
+use super::{Forge, Org, Repo};
+use std::collections::HashMap;
+
+/// Tokens used to authenticate with the forges hosting repos
+#[derive(Debug, Clone, Default)]
+pub struct Credentials {
+    pub github_token: Option<String>,
+    pub gitlab_token: Option<String>,
+    pub gitea_token: Option<String>,
+    /// Tokens for specific hosts; these take precedence over the forge-wide ones
+    pub host_tokens: HashMap<String, String>,
+}
+
+impl Credentials {
+    pub fn token_for(&self, repo: &Repo) -> Option<&str> {
+        if let Some(token) = self.host_tokens.get(repo.host()) {
+            return Some(token);
+        }
+
+        if !uses_forge_token(repo.forge, repo.host()) {
+            return None;
+        }
+
+        match repo.forge {
+            Forge::GitHub => self.github_token.as_deref(),
+            Forge::GitLab => self.gitlab_token.as_deref(),
+            Forge::Gitea => self.gitea_token.as_deref(),
+            Forge::Local => None,
+        }
+    }
+
+    pub fn token_for_org(&self, org: &Org) -> Option<&str> {
+        if let Some(token) = self.host_tokens.get(org.host()) {
+            return Some(token);
+        }
+
+        if !uses_forge_token(Forge::GitHub, org.host()) {
+            return None;
+        }
+
+        self.github_token.as_deref()
+    }
+}
+
+/// Whether the forge-wide token can be sent to a host; the GitHub and GitLab tokens are meant for
+/// github.com and gitlab.com, so other hosts (eg. GitHub Enterprise Server instances) only get a
+/// host specific token. Gitea has no default host, so its token is sent to any Gitea host.
+pub fn uses_forge_token(forge: Forge, host: &str) -> bool {
+    match forge {
+        Forge::GitHub => host == get_host_from_api_base_url(DEFAULT_API_BASE_URL),
+        Forge::GitLab => host == get_host_from_api_base_url(DEFAULT_GITLAB_API_BASE_URL),
+        Forge::Gitea => true,
+        Forge::Local => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::RawRepo;
+
+    fn get_repo(forge: Forge, api_base_url: Option<&str>) -> Repo {
+        Repo::try_from(RawRepo {
+            repo: Some("owner/repo".to_string()),
+            path: None,
+            forge: Some(forge),
+            host: None,
+            head_ref: None,
+            head_refs: None,
+            consider_prereleases: None,
+            api_base_url: api_base_url.map(|u| u.to_string()),
+            baseline: None,
+            tag_pattern: None,
+            packages: None,
+            label: None,
+        })
+        .expect("repo should've been valid")
+    }
+
+    fn get_credentials() -> Credentials {
+        Credentials {
+            github_token: Some("github-token".to_string()),
+            gitlab_token: Some("gitlab-token".to_string()),
+            gitea_token: Some("gitea-token".to_string()),
+            host_tokens: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn github_dot_com_repos_get_the_github_token() {
+        // GIVEN
+        let repo = get_repo(Forge::GitHub, None);
+        let credentials = get_credentials();
+
+        // WHEN
+        let result = credentials.token_for(&repo);
+
+        // THEN
+        assert_eq!(result, Some("github-token"));
+    }
+
+    #[test]
+    fn github_enterprise_server_repos_dont_get_the_github_token() {
+        // GIVEN
+        let repo = get_repo(Forge::GitHub, Some("https://github.example.com/api/v3"));
+        let credentials = get_credentials();
+
+        // WHEN
+        let result = credentials.token_for(&repo);
+
+        // THEN
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn github_enterprise_server_repos_get_their_host_token() {
+        // GIVEN
+        let repo = get_repo(Forge::GitHub, Some("https://github.example.com/api/v3"));
+        let mut credentials = get_credentials();
+        credentials
+            .host_tokens
+            .insert("github.example.com".to_string(), "host-token".to_string());
+
+        // WHEN
+        let result = credentials.token_for(&repo);
+
+        // THEN
+        assert_eq!(result, Some("host-token"));
+    }
+
+    #[test]
+    fn self_managed_gitlab_repos_dont_get_the_gitlab_token() {
+        // GIVEN
+        let repo = get_repo(Forge::GitLab, Some("https://gitlab.example.com/api/v4"));
+        let credentials = get_credentials();
+
+        // WHEN
+        let result = credentials.token_for(&repo);
+
+        // THEN
+        assert_eq!(result, None);
+    }
+}
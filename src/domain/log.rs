@@ -3,22 +3,97 @@ use super::{Commit, Repo};
 #[derive(Debug)]
 pub struct CommitLog {
     pub repo: Repo,
+    pub package: Option<String>,
     pub base_ref: String,
     pub head_ref: String,
     pub commits: Vec<Commit>,
     pub html_url: String,
+    pub total_commits: usize,
+    pub ahead_by: usize,
+    pub behind_by: usize,
+    /// Set when only some of the commits in the range could be checked (eg. for a package, when
+    /// the repo's compare was truncated), so that there may be more commits than total_commits
+    pub total_is_lower_bound: bool,
+}
+
+impl CommitLog {
+    pub fn is_truncated(&self) -> bool {
+        self.commits.len() < self.total_commits
+    }
+}
+
+/// The most recent commits on the head ref of a repo (or package) that has never been released
+#[derive(Debug)]
+pub struct NeverReleasedLog {
+    pub repo: Repo,
+    pub package: Option<String>,
+    pub head_ref: String,
+    pub commits: Vec<Commit>,
+    pub html_url: String,
+}
+
+#[derive(Debug)]
+pub enum RepoChangelog {
+    Released(CommitLog),
+    NeverReleased(NeverReleasedLog),
 }
 
 #[derive(Debug)]
 pub struct CommitLogResults {
     pub logs: Vec<CommitLog>,
+    pub never_released_logs: Vec<NeverReleasedLog>,
     pub errors: CommitLogFetchErrors,
 }
 
 #[derive(Debug)]
 pub enum CommitLogFetchError {
-    Repo { app: Repo, error: anyhow::Error },
-    System { error: anyhow::Error },
+    Repo {
+        app: Box<Repo>,
+        kind: FetchErrorKind,
+        error: anyhow::Error,
+    },
+    System {
+        kind: FetchErrorKind,
+        error: anyhow::Error,
+    },
+}
+
+/// What went wrong, so that tools consuming reports can tell errors apart without parsing messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchErrorKind {
+    /// A request (or git command) failed, or its response couldn't be used
+    Fetch,
+    /// A request timed out
+    Timeout,
+    /// The run's deadline passed before the repo's changelog was fetched
+    Deadline,
+    /// An org's repos couldn't be listed
+    Discovery,
+    /// Something went wrong within unreleased itself
+    Internal,
+}
+
+impl CommitLogFetchError {
+    pub fn repo(&self) -> Option<&Repo> {
+        match self {
+            CommitLogFetchError::Repo { app, .. } => Some(app),
+            CommitLogFetchError::System { .. } => None,
+        }
+    }
+
+    pub fn kind(&self) -> FetchErrorKind {
+        match self {
+            CommitLogFetchError::Repo { kind, .. } => *kind,
+            CommitLogFetchError::System { kind, .. } => *kind,
+        }
+    }
+
+    pub fn error(&self) -> &anyhow::Error {
+        match self {
+            CommitLogFetchError::Repo { error, .. } => error,
+            CommitLogFetchError::System { error, .. } => error,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -31,17 +106,30 @@ impl CommitLogFetchErrors {
         Self { errors: Vec::new() }
     }
 
-    pub fn add_repo_error(&mut self, app: Repo, error: anyhow::Error) {
-        self.errors.push(CommitLogFetchError::Repo { app, error });
+    pub fn add_repo_error(&mut self, app: Repo, kind: FetchErrorKind, error: anyhow::Error) {
+        self.errors.push(CommitLogFetchError::Repo {
+            app: Box::new(app),
+            kind,
+            error,
+        });
+    }
+
+    pub fn add_system_error(&mut self, kind: FetchErrorKind, error: anyhow::Error) {
+        self.errors
+            .push(CommitLogFetchError::System { kind, error });
     }
 
-    pub fn add_system_error(&mut self, error: anyhow::Error) {
-        self.errors.push(CommitLogFetchError::System { error });
+    pub fn extend(&mut self, other: CommitLogFetchErrors) {
+        self.errors.extend(other.errors);
     }
 
     pub fn is_empty(&self) -> bool {
         self.errors.is_empty()
     }
+
+    pub fn errors(&self) -> &[CommitLogFetchError] {
+        &self.errors
+    }
 }
 
 impl std::fmt::Display for CommitLogFetchErrors {
@@ -49,11 +137,12 @@ impl std::fmt::Display for CommitLogFetchErrors {
         writeln!(f, "couldn't fetch commit logs for some repos:")?;
         for error in &self.errors {
             match error {
-                CommitLogFetchError::Repo { app, error } => {
-                    writeln!(f, " - {}: {}", app, error)?;
-                }
-                CommitLogFetchError::System { error } => {
-                    writeln!(f, " - system error: {}", error)?;
+                CommitLogFetchError::Repo { app, error, .. } => match &app.label {
+                    Some(label) => writeln!(f, " - {} ({}): {:#}", app, label, error)?,
+                    None => writeln!(f, " - {}: {:#}", app, error)?,
+                },
+                CommitLogFetchError::System { error, .. } => {
+                    writeln!(f, " - system error: {:#}", error)?;
                 }
             }
         }
@@ -1,11 +1,21 @@
 mod commit;
 mod config;
+mod credentials;
 mod log;
+mod org;
+mod package;
+mod quota;
 mod release;
 mod repo;
+mod tag;
 
 pub use commit::*;
 pub use config::*;
+pub use credentials::*;
 pub use log::*;
+pub use org::*;
+pub use package::*;
+pub use quota::*;
 pub use release::*;
 pub use repo::*;
+pub use tag::*;
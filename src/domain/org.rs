@@ -0,0 +1,318 @@
+use super::repo::{
+    API_BASE_URL_ERROR, DEFAULT_API_BASE_URL, RawRepo, RepoDefaults, get_host_from_api_base_url,
+    parse_api_base_url,
+};
+use regex::Regex;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct RawOrg {
+    pub name: String,
+    pub kind: Option<OwnerKind>,
+    pub api_base_url: Option<String>,
+    pub topics: Option<Vec<String>>,
+    pub visibility: Option<Visibility>,
+    pub include_archived: Option<bool>,
+    pub include_forks: Option<bool>,
+    pub name_pattern: Option<String>,
+}
+
+/// A GitHub organization (or user) whose repos are discovered via the API
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct Org {
+    pub name: String,
+    pub kind: OwnerKind,
+    pub api_base_url: String,
+    /// Repos need to have at least one of these topics; all repos are included if this is empty
+    pub topics: Vec<String>,
+    /// Repos with any visibility are included if this is not set
+    pub visibility: Option<Visibility>,
+    pub include_archived: bool,
+    pub include_forks: bool,
+    pub name_pattern: Option<NamePattern>,
+    /// Settings that discovered repos fall back to
+    pub repo_defaults: RepoDefaults,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Eq, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(rename_all = "lowercase")]
+pub enum OwnerKind {
+    #[default]
+    Org,
+    User,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Eq, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    Public,
+    Private,
+    Internal,
+}
+
+#[derive(Debug, Clone)]
+pub struct NamePattern(Regex);
+
+impl PartialEq for NamePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for NamePattern {}
+
+#[cfg(test)]
+impl serde::Serialize for NamePattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+/// A repo listed for an org by the GitHub API
+#[derive(Debug, Deserialize)]
+pub struct DiscoveredRepo {
+    pub name: String,
+    pub full_name: String,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    pub visibility: Visibility,
+    pub archived: bool,
+    pub fork: bool,
+    pub default_branch: String,
+}
+
+impl Org {
+    pub fn repos_api_url(&self) -> String {
+        match self.kind {
+            OwnerKind::Org => format!("{}/orgs/{}/repos", self.api_base_url, self.name),
+            OwnerKind::User => format!("{}/users/{}/repos", self.api_base_url, self.name),
+        }
+    }
+
+    pub fn host(&self) -> &str {
+        get_host_from_api_base_url(&self.api_base_url)
+    }
+
+    pub fn includes(&self, repo: &DiscoveredRepo) -> bool {
+        if repo.archived && !self.include_archived {
+            return false;
+        }
+
+        if repo.fork && !self.include_forks {
+            return false;
+        }
+
+        if self.visibility.is_some_and(|v| v != repo.visibility) {
+            return false;
+        }
+
+        if !self.topics.is_empty() && !repo.topics.iter().any(|t| self.topics.contains(t)) {
+            return false;
+        }
+
+        self.name_pattern
+            .as_ref()
+            .is_none_or(|pattern| pattern.0.is_match(&repo.name))
+    }
+
+    // discovered repos are compared against their default branch, unless the defaults set a head ref
+    pub fn get_raw_repo(&self, repo: &DiscoveredRepo) -> RawRepo {
+        let mut raw_repo = RawRepo {
+            repo: Some(repo.full_name.clone()),
+            path: None,
+            forge: None,
+            host: None,
+            head_ref: None,
+            head_refs: None,
+            consider_prereleases: None,
+            api_base_url: Some(self.api_base_url.clone()),
+            baseline: None,
+            tag_pattern: None,
+            packages: None,
+            label: None,
+        };
+        raw_repo.apply_defaults(&self.repo_defaults);
+
+        raw_repo
+    }
+}
+
+#[derive(Debug)]
+pub struct OrgValidationError {
+    errors: Vec<&'static str>,
+}
+
+impl std::fmt::Display for OrgValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for error in &self.errors {
+            writeln!(f, "   - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<RawOrg> for Org {
+    type Error = OrgValidationError;
+
+    fn try_from(value: RawOrg) -> Result<Self, Self::Error> {
+        let mut errors = Vec::new();
+
+        let name = value.name.trim();
+        if name.is_empty() {
+            errors.push("org name is empty");
+        } else if name.contains('/') {
+            errors.push("org name cannot contain \"/\"");
+        }
+
+        let api_base_url = match value.api_base_url.as_deref() {
+            Some(url) => match parse_api_base_url(url) {
+                Some(u) => u.to_string(),
+                None => {
+                    errors.push(API_BASE_URL_ERROR);
+                    String::new()
+                }
+            },
+            None => DEFAULT_API_BASE_URL.to_string(),
+        };
+
+        let topics = value
+            .topics
+            .unwrap_or_default()
+            .iter()
+            .map(|t| t.trim().to_string())
+            .collect::<Vec<_>>();
+        if topics.iter().any(|t| t.is_empty()) {
+            errors.push("topics cannot be empty");
+        }
+
+        let name_pattern = match value.name_pattern.as_deref().map(Regex::new) {
+            Some(Ok(regex)) => Some(NamePattern(regex)),
+            Some(Err(_)) => {
+                errors.push("name_pattern is not a valid regex");
+                None
+            }
+            None => None,
+        };
+
+        if !errors.is_empty() {
+            return Err(OrgValidationError { errors });
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            kind: value.kind.unwrap_or_default(),
+            api_base_url,
+            topics,
+            visibility: value.visibility,
+            include_archived: value.include_archived.unwrap_or(false),
+            include_forks: value.include_forks.unwrap_or(false),
+            name_pattern,
+            repo_defaults: RepoDefaults::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_org(raw: RawOrg) -> Org {
+        Org::try_from(raw).expect("org should've been valid")
+    }
+
+    fn get_raw_org() -> RawOrg {
+        RawOrg {
+            name: "owner".to_string(),
+            kind: None,
+            api_base_url: None,
+            topics: None,
+            visibility: None,
+            include_archived: None,
+            include_forks: None,
+            name_pattern: None,
+        }
+    }
+
+    fn get_discovered_repo(name: &str) -> DiscoveredRepo {
+        DiscoveredRepo {
+            name: name.to_string(),
+            full_name: format!("owner/{}", name),
+            topics: vec![],
+            visibility: Visibility::Public,
+            archived: false,
+            fork: false,
+            default_branch: "main".to_string(),
+        }
+    }
+
+    #[test]
+    fn archived_repos_and_forks_are_excluded_by_default() {
+        // GIVEN
+        let org = get_org(get_raw_org());
+        let mut archived = get_discovered_repo("archived");
+        archived.archived = true;
+        let mut fork = get_discovered_repo("fork");
+        fork.fork = true;
+
+        // WHEN
+        // THEN
+        assert!(org.includes(&get_discovered_repo("active")));
+        assert!(!org.includes(&archived));
+        assert!(!org.includes(&fork));
+    }
+
+    #[test]
+    fn repos_can_be_filtered_by_topics_visibility_and_name() {
+        // GIVEN
+        let org = get_org(RawOrg {
+            topics: Some(vec!["service".to_string(), "library".to_string()]),
+            visibility: Some(Visibility::Private),
+            name_pattern: Some("^svc-".to_string()),
+            ..get_raw_org()
+        });
+        let mut matching = get_discovered_repo("svc-billing");
+        matching.topics = vec!["rust".to_string(), "service".to_string()];
+        matching.visibility = Visibility::Private;
+        let mut without_topic = get_discovered_repo("svc-auth");
+        without_topic.visibility = Visibility::Private;
+        let mut public = get_discovered_repo("svc-docs");
+        public.topics = vec!["service".to_string()];
+        let mut wrong_name = get_discovered_repo("billing");
+        wrong_name.topics = vec!["library".to_string()];
+        wrong_name.visibility = Visibility::Private;
+
+        // WHEN
+        // THEN
+        assert!(org.includes(&matching));
+        assert!(!org.includes(&without_topic));
+        assert!(!org.includes(&public));
+        assert!(!org.includes(&wrong_name));
+    }
+
+    #[test]
+    fn repos_api_url_for_users_works() {
+        // GIVEN
+        let org = get_org(RawOrg {
+            kind: Some(OwnerKind::User),
+            api_base_url: Some("https://github.example.com/api/v3".to_string()),
+            ..get_raw_org()
+        });
+
+        // WHEN
+        let result = org.repos_api_url();
+
+        // THEN
+        assert_eq!(
+            result,
+            "https://github.example.com/api/v3/users/owner/repos"
+        );
+    }
+}
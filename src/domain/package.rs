@@ -0,0 +1,58 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct RawPackage {
+    pub name: String,
+    pub tag_prefix: String,
+    pub paths: Option<Vec<String>>,
+}
+
+/// A separately tagged part of a monorepo
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct Package {
+    pub name: String,
+    pub tag_prefix: String,
+    /// Only commits touching these paths are reported; all commits are reported if this is empty
+    pub paths: Vec<String>,
+}
+
+impl TryFrom<RawPackage> for Package {
+    type Error = Vec<&'static str>;
+
+    fn try_from(value: RawPackage) -> Result<Self, Self::Error> {
+        let mut errors = Vec::new();
+
+        let name = value.name.trim();
+        if name.is_empty() {
+            errors.push("package name is empty");
+        }
+
+        let tag_prefix = value.tag_prefix.trim();
+        if tag_prefix.is_empty() {
+            errors.push("package tag_prefix is empty");
+        }
+
+        let paths = value
+            .paths
+            .unwrap_or_default()
+            .iter()
+            .map(|p| p.trim().trim_matches('/').to_string())
+            .collect::<Vec<_>>();
+        if paths.iter().any(|p| p.is_empty()) {
+            errors.push("package paths cannot be empty");
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            tag_prefix: tag_prefix.to_string(),
+            paths,
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use std::fmt::Display;
+
+/// The API rate limit of a host, as last reported by it
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ApiQuota {
+    pub host: String,
+    /// The API the quota applies to, for hosts that have several (eg. GitHub's REST and GraphQL
+    /// APIs)
+    pub resource: Option<String>,
+    pub limit: u64,
+    pub remaining: u64,
+    pub resets_at: DateTime<Utc>,
+}
+
+impl Display for ApiQuota {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.resource {
+            Some(resource) => write!(f, "{} ({})", self.host, resource)?,
+            None => write!(f, "{}", self.host)?,
+        }
+
+        write!(
+            f,
+            ": {}/{} requests remaining, resets at {}",
+            self.remaining,
+            self.limit,
+            self.resets_at.format("%Y-%m-%d %H:%M:%S UTC")
+        )
+    }
+}
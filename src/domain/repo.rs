@@ -1,28 +1,338 @@
+use super::package::{Package, RawPackage};
+use regex::Regex;
 use serde::Deserialize;
+use std::collections::HashSet;
 use std::fmt::{self, Display};
 
-const DEFAULT_HEAD_REF: &str = "main";
+// points to the default branch, both on forges and in local checkouts
+const DEFAULT_BRANCH_REF: &str = "HEAD";
+pub(super) const DEFAULT_API_BASE_URL: &str = "https://api.github.com";
+pub(super) const DEFAULT_GITLAB_API_BASE_URL: &str = "https://gitlab.com/api/v4";
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
 pub struct RawRepo {
-    pub repo: String,
+    pub repo: Option<String>,
+    pub path: Option<String>,
+    pub forge: Option<Forge>,
+    pub host: Option<String>,
     pub head_ref: Option<String>,
+    pub head_refs: Option<Vec<RawHeadRef>>,
     pub consider_prereleases: Option<bool>,
+    pub api_base_url: Option<String>,
+    pub baseline: Option<Baseline>,
+    pub tag_pattern: Option<String>,
+    pub packages: Option<Vec<RawPackage>>,
+    pub label: Option<String>,
+}
+
+/// One of several head refs tracked for a repo; either just the ref, or the ref along with a
+/// pattern for the tags of the release line it's compared against
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(untagged)]
+pub enum RawHeadRef {
+    Name(String),
+    WithTagPattern(RawHeadRefWithTagPattern),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct RawHeadRefWithTagPattern {
+    #[serde(rename = "ref")]
+    pub name: String,
+    pub tag_pattern: Option<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(test, derive(serde::Serialize))]
 pub struct Repo {
+    /// Empty for local repos
     pub owner: String,
+    /// The path of the repo on disk for local repos
     pub repo: String,
-    pub head_ref: String,
+    pub forge: Forge,
+    pub head_ref: HeadRef,
     pub consider_prereleases: bool,
+    pub api_base_url: String,
+    pub baseline: Baseline,
+    pub tag_pattern: Option<TagPattern>,
+    pub packages: Vec<Package>,
+    /// Name of the group the repo is listed under, if any
+    pub group: Option<String>,
+    /// Tells apart entries for the same repo that track different head refs
+    pub label: Option<String>,
+}
+
+/// The ref whose unreleased commits are reported
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub enum HeadRef {
+    Configured(String),
+    /// Used when no head ref is configured; the branch's name is looked up before fetching
+    /// commits, unless it's already known (eg. for repos discovered via orgs)
+    DefaultBranch(Option<String>),
+}
+
+impl HeadRef {
+    /// The ref to pass to forges and git; a default branch whose name isn't known yet is
+    /// referred to via HEAD
+    pub fn name(&self) -> &str {
+        match self {
+            HeadRef::Configured(name) | HeadRef::DefaultBranch(Some(name)) => name,
+            HeadRef::DefaultBranch(None) => DEFAULT_BRANCH_REF,
+        }
+    }
+}
+
+impl Display for HeadRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeadRef::DefaultBranch(None) => write!(f, "default branch"),
+            _ => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+/// What forges return for a repo; only the parts needed are deserialized
+#[derive(Debug, Deserialize)]
+pub struct RepoDetails {
+    /// Empty repos don't have one
+    pub default_branch: Option<String>,
+}
+
+/// Settings that repos fall back to when they don't set them
+#[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct RepoDefaults {
+    pub head_ref: Option<String>,
+    pub consider_prereleases: Option<bool>,
+    pub baseline: Option<Baseline>,
+}
+
+impl RepoDefaults {
+    /// Settings set on self take precedence over the ones in fallback
+    pub(super) fn or(&self, fallback: &RepoDefaults) -> RepoDefaults {
+        RepoDefaults {
+            head_ref: self.head_ref.clone().or_else(|| fallback.head_ref.clone()),
+            consider_prereleases: self.consider_prereleases.or(fallback.consider_prereleases),
+            baseline: self.baseline.or(fallback.baseline),
+        }
+    }
+}
+
+impl RawRepo {
+    pub(super) fn name(&self) -> Option<&str> {
+        self.repo.as_deref().or(self.path.as_deref())
+    }
+
+    pub(super) fn apply_defaults(&mut self, defaults: &RepoDefaults) {
+        if self.head_ref.is_none() && self.head_refs.is_none() {
+            self.head_ref = defaults.head_ref.clone();
+        }
+
+        if self.consider_prereleases.is_none() {
+            self.consider_prereleases = defaults.consider_prereleases;
+        }
+
+        // local repos and monorepos always use tags as their baseline unless configured otherwise
+        if self.baseline.is_none() && self.path.is_none() && self.packages.is_none() {
+            self.baseline = defaults.baseline;
+        }
+    }
+}
+
+impl RawRepo {
+    /// Repos tracking several head refs turn into one repo per head ref; these get told apart by
+    /// their labels (see Config)
+    pub(super) fn try_into_repos(mut self) -> Result<Vec<Repo>, RepoValidationError> {
+        let Some(head_refs) = self.head_refs.take() else {
+            return Repo::try_from(self).map(|repo| vec![repo]);
+        };
+
+        let mut errors = RepoValidationError::new();
+        if self.head_ref.is_some() {
+            errors.add_error("only one of head_ref and head_refs can be set");
+        }
+        if self.label.is_some() {
+            errors.add_error("label can't be set along with head_refs");
+        }
+        if head_refs.is_empty() {
+            errors.add_error("head_refs is empty");
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut repos = Vec::new();
+        for head_ref in head_refs {
+            let mut raw_repo = self.clone();
+            match head_ref {
+                RawHeadRef::Name(name) => raw_repo.head_ref = Some(name),
+                RawHeadRef::WithTagPattern(head_ref) => {
+                    raw_repo.head_ref = Some(head_ref.name);
+                    // a release line is made up of tags, so it overrides the repo's baseline
+                    if let Some(pattern) = head_ref.tag_pattern {
+                        raw_repo.tag_pattern = Some(pattern);
+                        raw_repo.baseline = Some(Baseline::Tag);
+                    }
+                }
+            }
+
+            match Repo::try_from(raw_repo) {
+                Ok(repo) => repos.push(repo),
+                Err(e) => errors.merge(e),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(repos)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// The service hosting a repo
+#[derive(Debug, Clone, Copy, Default, Deserialize, Eq, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(rename_all = "lowercase")]
+pub enum Forge {
+    #[default]
+    GitHub,
+    GitLab,
+    /// Gitea, or its fork Forgejo
+    #[serde(alias = "forgejo")]
+    Gitea,
+    /// A git repo on disk, read without any forge API; repos configured with a path use this
+    #[serde(skip_deserializing)]
+    Local,
+}
+
+/// What the commits on the head ref are compared against
+#[derive(Debug, Clone, Copy, Default, Deserialize, Eq, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(rename_all = "lowercase")]
+pub enum Baseline {
+    /// The latest GitHub release
+    #[default]
+    Release,
+    /// The highest tag by semver order
+    Tag,
+}
+
+#[derive(Debug, Clone)]
+pub struct TagPattern(Regex);
+
+impl TagPattern {
+    pub fn is_match(&self, tag: &str) -> bool {
+        self.0.is_match(tag)
+    }
+}
+
+impl PartialEq for TagPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for TagPattern {}
+
+#[cfg(test)]
+impl serde::Serialize for TagPattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+impl Repo {
+    pub fn api_url(&self) -> String {
+        match self.forge {
+            Forge::GitHub => format!("{}/repos/{}/{}", self.api_base_url, self.owner, self.repo),
+            // GitLab identifies projects by their URL-encoded path, which can include subgroups
+            Forge::GitLab => format!(
+                "{}/projects/{}%2F{}",
+                self.api_base_url,
+                self.owner,
+                self.repo.replace('/', "%2F")
+            ),
+            Forge::Gitea => format!("{}/repos/{}/{}", self.api_base_url, self.owner, self.repo),
+            Forge::Local => String::new(),
+        }
+    }
+
+    // local repos don't have web URLs; views skip empty URLs
+    pub fn web_url(&self) -> String {
+        if self.forge == Forge::Local {
+            return String::new();
+        }
+
+        let web_base_url = match (self.forge, self.api_base_url.as_str()) {
+            (Forge::GitHub, DEFAULT_API_BASE_URL) => "https://github.com",
+            (Forge::GitHub, url) => url.strip_suffix("/api/v3").unwrap_or(url),
+            (Forge::GitLab, url) => url.strip_suffix("/api/v4").unwrap_or(url),
+            (Forge::Gitea, url) => url.strip_suffix("/api/v1").unwrap_or(url),
+            (Forge::Local, _) => "",
+        };
+
+        format!("{}/{}/{}", web_base_url, self.owner, self.repo)
+    }
+
+    pub fn compare_web_url(&self, base_ref: &str, head_ref: &str) -> String {
+        let (base_ref, head_ref) = (encode_ref(base_ref), encode_ref(head_ref));
+        match self.forge {
+            Forge::GitHub => format!("{}/compare/{}...{}", self.web_url(), base_ref, head_ref),
+            Forge::GitLab => format!("{}/-/compare/{}...{}", self.web_url(), base_ref, head_ref),
+            Forge::Gitea => format!("{}/compare/{}...{}", self.web_url(), base_ref, head_ref),
+            Forge::Local => String::new(),
+        }
+    }
+
+    pub fn commits_web_url(&self, head_ref: &str) -> String {
+        let head_ref = encode_ref(head_ref);
+        match self.forge {
+            Forge::GitHub => format!("{}/commits/{}", self.web_url(), head_ref),
+            Forge::GitLab => format!("{}/-/commits/{}", self.web_url(), head_ref),
+            Forge::Gitea => format!("{}/commits/branch/{}", self.web_url(), head_ref),
+            Forge::Local => String::new(),
+        }
+    }
+
+    /// The host (and port, if any) of the repo's forge; used to look up per-host tokens
+    pub fn host(&self) -> &str {
+        get_host_from_api_base_url(&self.api_base_url)
+    }
+
+    /// Whether both point to the same repo, regardless of the head ref they track; forges treat
+    /// owner and repo names case-insensitively, but paths of local repos are compared as is
+    pub fn is_same_repo(&self, other: &Repo) -> bool {
+        if self.forge != other.forge || self.api_base_url != other.api_base_url {
+            return false;
+        }
+
+        match self.forge {
+            Forge::Local => self.repo == other.repo,
+            _ => {
+                self.owner.eq_ignore_ascii_case(&other.owner)
+                    && self.repo.eq_ignore_ascii_case(&other.repo)
+            }
+        }
+    }
 }
 
 impl Ord for Repo {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        (self.owner.as_str(), self.repo.as_str()).cmp(&(other.owner.as_str(), other.repo.as_str()))
+        (self.owner.as_str(), self.repo.as_str(), &self.label).cmp(&(
+            other.owner.as_str(),
+            other.repo.as_str(),
+            &other.label,
+        ))
     }
 }
 
@@ -34,7 +344,10 @@ impl PartialOrd for Repo {
 
 impl Display for Repo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}/{}", self.owner, self.repo)
+        match self.forge {
+            Forge::Local => write!(f, "{}", self.repo),
+            _ => write!(f, "{}/{}", self.owner, self.repo),
+        }
     }
 }
 
@@ -44,13 +357,26 @@ pub struct RepoValidationError {
 }
 
 impl RepoValidationError {
-    fn new() -> Self {
+    pub(super) fn new() -> Self {
         Self { errors: Vec::new() }
     }
 
-    fn add_error(&mut self, message: &'static str) {
+    pub(super) fn add_error(&mut self, message: &'static str) {
         self.errors.push(message);
     }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    // the same problem is only reported once, even if several head refs run into it
+    fn merge(&mut self, other: RepoValidationError) {
+        for error in other.errors {
+            if !self.errors.contains(&error) {
+                self.errors.push(error);
+            }
+        }
+    }
 }
 
 impl std::fmt::Display for RepoValidationError {
@@ -68,24 +394,43 @@ impl TryFrom<RawRepo> for Repo {
     fn try_from(value: RawRepo) -> Result<Self, Self::Error> {
         let mut errors = RepoValidationError::new();
 
-        let (maybe_owner, maybe_repo) = match value.repo.trim().split_once("/") {
-            Some(("", "")) => {
-                errors.add_error("repo is empty");
-                (None, None)
-            }
-            Some((_, "")) => {
-                errors.add_error("repo name is empty");
-                (None, None)
-            }
-            Some(("", _)) => {
-                errors.add_error("owner is empty");
+        let is_local = value.path.is_some();
+
+        let (maybe_owner, maybe_repo) = match (value.repo.as_deref(), value.path.as_deref()) {
+            (Some(_), Some(_)) => {
+                errors.add_error("only one of repo and path can be set");
                 (None, None)
             }
-            Some((owner, repo)) => (Some(owner), Some(repo)),
-            None => {
-                errors.add_error(r#"repo needs to be in the format "owner/repo""#);
+            (None, None) => {
+                errors.add_error("one of repo and path needs to be set");
                 (None, None)
             }
+            (None, Some(path)) => match path.trim() {
+                "" => {
+                    errors.add_error("path is empty");
+                    (None, None)
+                }
+                path => (Some(""), Some(path)),
+            },
+            (Some(repo), None) => match repo.trim().split_once("/") {
+                Some(("", "")) => {
+                    errors.add_error("repo is empty");
+                    (None, None)
+                }
+                Some((_, "")) => {
+                    errors.add_error("repo name is empty");
+                    (None, None)
+                }
+                Some(("", _)) => {
+                    errors.add_error("owner is empty");
+                    (None, None)
+                }
+                Some((owner, repo)) => (Some(owner), Some(repo)),
+                None => {
+                    errors.add_error(r#"repo needs to be in the format "owner/repo""#);
+                    (None, None)
+                }
+            },
         };
 
         let maybe_head_ref = match value.head_ref.as_deref().map(|r| r.trim()) {
@@ -93,18 +438,347 @@ impl TryFrom<RawRepo> for Repo {
                 errors.add_error("head_ref is empty");
                 None
             }
-            Some(r) => Some(r),
-            None => Some(DEFAULT_HEAD_REF),
+            Some(r) => Some(HeadRef::Configured(r.to_string())),
+            None => Some(HeadRef::DefaultBranch(None)),
         };
 
-        match (maybe_owner, maybe_repo, maybe_head_ref) {
-            (Some(owner), Some(repo), Some(head_ref)) => Ok(Repo {
+        let forge = if is_local {
+            if value.forge.is_some() || value.host.is_some() || value.api_base_url.is_some() {
+                errors.add_error("forge, host, and api_base_url can't be set for local repos");
+            }
+            Forge::Local
+        } else {
+            value.forge.unwrap_or_default()
+        };
+
+        let maybe_api_base_url = match (value.api_base_url.as_deref(), value.host.as_deref()) {
+            _ if is_local => Some(String::new()),
+            (Some(_), Some(_)) => {
+                errors.add_error("only one of api_base_url and host can be set");
+                None
+            }
+            (Some(url), None) => match parse_api_base_url(url) {
+                Some(u) => Some(u.to_string()),
+                None => {
+                    errors.add_error(API_BASE_URL_ERROR);
+                    None
+                }
+            },
+            (None, Some(host)) => match get_api_base_url_for_host(forge, host) {
+                Some(u) => Some(u),
+                None => {
+                    errors.add_error(r#"host needs to be a hostname, eg. "git.example.com""#);
+                    None
+                }
+            },
+            (None, None) => match forge {
+                Forge::GitHub => Some(DEFAULT_API_BASE_URL.to_string()),
+                Forge::GitLab => Some(DEFAULT_GITLAB_API_BASE_URL.to_string()),
+                // Gitea and Forgejo are always self-hosted
+                Forge::Gitea => {
+                    errors.add_error("host needs to be set for Gitea/Forgejo repos");
+                    None
+                }
+                Forge::Local => Some(String::new()),
+            },
+        };
+
+        // local repos don't have releases, only tags
+        let baseline = match (value.baseline, &value.packages) {
+            (Some(Baseline::Release), _) if is_local => {
+                errors.add_error(r#"baseline needs to be "tag" for local repos"#);
+                Baseline::Release
+            }
+            (Some(Baseline::Release), Some(_)) => {
+                errors.add_error(r#"packages can only be used when baseline is "tag""#);
+                Baseline::Release
+            }
+            (Some(b), _) => b,
+            (None, _) if is_local => Baseline::Tag,
+            (None, Some(_)) => Baseline::Tag,
+            (None, None) => Baseline::default(),
+        };
+
+        let maybe_tag_pattern = match value.tag_pattern.as_deref() {
+            Some(_) if baseline != Baseline::Tag => {
+                errors.add_error(r#"tag_pattern can only be used when baseline is "tag""#);
+                None
+            }
+            Some(pattern) => match Regex::new(pattern) {
+                Ok(regex) => Some(Some(TagPattern(regex))),
+                Err(_) => {
+                    errors.add_error("tag_pattern is not a valid regex");
+                    None
+                }
+            },
+            None => Some(None),
+        };
+
+        let maybe_label = match value.label.as_deref().map(|l| l.trim()) {
+            Some("") => {
+                errors.add_error("label is empty");
+                None
+            }
+            l => Some(l.map(|l| l.to_string())),
+        };
+
+        let mut packages = Vec::new();
+        let mut package_names = HashSet::new();
+        for raw_package in value.packages.unwrap_or_default() {
+            match Package::try_from(raw_package) {
+                Ok(package) => {
+                    if !package_names.insert(package.name.clone()) {
+                        errors.add_error("package names need to be unique");
+                    }
+                    packages.push(package);
+                }
+                Err(package_errors) => {
+                    for error in package_errors {
+                        errors.add_error(error);
+                    }
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        match (
+            maybe_owner,
+            maybe_repo,
+            maybe_head_ref,
+            maybe_api_base_url,
+            maybe_tag_pattern,
+            maybe_label,
+        ) {
+            (
+                Some(owner),
+                Some(repo),
+                Some(head_ref),
+                Some(api_base_url),
+                Some(tag_pattern),
+                Some(label),
+            ) => Ok(Repo {
                 owner: owner.to_string(),
                 repo: repo.to_string(),
-                head_ref: head_ref.to_string(),
+                forge,
+                head_ref,
                 consider_prereleases: value.consider_prereleases.unwrap_or(false),
+                api_base_url,
+                baseline,
+                tag_pattern,
+                packages,
+                group: None,
+                label,
             }),
             _ => Err(errors),
         }
     }
 }
+
+pub(super) const API_BASE_URL_ERROR: &str =
+    r#"api_base_url needs to be an HTTP(S) URL, eg. "https://github.example.com/api/v3""#;
+
+pub(super) fn parse_api_base_url(url: &str) -> Option<&str> {
+    let url = url.trim().trim_end_matches('/');
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+
+    if host.is_empty() { None } else { Some(url) }
+}
+
+/// Percent-encodes a ref for use in a URL path; slashes are kept, since forges take refs like
+/// "release/1.x" as is, but anything that could end the path or change its meaning (eg. "#", "?",
+/// "%", or "+") is encoded
+pub fn encode_ref(name: &str) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    encoded
+}
+
+pub(super) fn get_host_from_api_base_url(url: &str) -> &str {
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+
+    url.split('/').next().unwrap_or(url)
+}
+
+// the scheme can be omitted from hosts, in which case HTTPS is used
+fn get_api_base_url_for_host(forge: Forge, host: &str) -> Option<String> {
+    let host = host.trim();
+    let (scheme, host) = if let Some(h) = host.strip_prefix("http://") {
+        ("http", h)
+    } else {
+        ("https", host.strip_prefix("https://").unwrap_or(host))
+    };
+    let host = host.trim_end_matches('/');
+    if host.is_empty() || host.contains(char::is_whitespace) {
+        return None;
+    }
+    let base_url = format!("{}://{}", scheme, host);
+
+    let api_base_url = match (forge, base_url.as_str()) {
+        (Forge::GitHub, "https://github.com") => DEFAULT_API_BASE_URL.to_string(),
+        (Forge::GitHub, _) => format!("{}/api/v3", base_url),
+        (Forge::GitLab, _) => format!("{}/api/v4", base_url),
+        (Forge::Gitea, _) => format!("{}/api/v1", base_url),
+        (Forge::Local, _) => return None,
+    };
+
+    Some(api_base_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_repo(api_base_url: Option<&str>) -> Repo {
+        get_repo_on_forge(None, None, api_base_url)
+    }
+
+    fn get_repo_on_forge(
+        forge: Option<Forge>,
+        host: Option<&str>,
+        api_base_url: Option<&str>,
+    ) -> Repo {
+        Repo::try_from(RawRepo {
+            repo: Some("owner/repo".to_string()),
+            path: None,
+            forge,
+            host: host.map(|h| h.to_string()),
+            head_ref: None,
+            head_refs: None,
+            consider_prereleases: None,
+            api_base_url: api_base_url.map(|u| u.to_string()),
+            baseline: None,
+            tag_pattern: None,
+            packages: None,
+            label: None,
+        })
+        .expect("repo should've been valid")
+    }
+
+    #[test]
+    fn web_url_for_github_dot_com_works() {
+        // GIVEN
+        let repo = get_repo(None);
+
+        // WHEN
+        let result = repo.web_url();
+
+        // THEN
+        assert_eq!(result, "https://github.com/owner/repo");
+    }
+
+    #[test]
+    fn web_url_for_github_enterprise_server_works() {
+        // GIVEN
+        let repo = get_repo(Some("https://github.example.com/api/v3/"));
+
+        // WHEN
+        let result = repo.web_url();
+
+        // THEN
+        assert_eq!(result, "https://github.example.com/owner/repo");
+    }
+
+    #[test]
+    fn refs_in_web_urls_are_percent_encoded() {
+        // GIVEN
+        let repo = get_repo(None);
+
+        // WHEN
+        let compare_url = repo.compare_web_url("v1.0.0+build.1", "release/1.x#wip");
+        let commits_url = repo.commits_web_url("feature/100%?");
+
+        // THEN
+        assert_eq!(
+            compare_url,
+            "https://github.com/owner/repo/compare/v1.0.0%2Bbuild.1...release/1.x%23wip"
+        );
+        assert_eq!(
+            commits_url,
+            "https://github.com/owner/repo/commits/feature/100%25%3F"
+        );
+    }
+
+    #[test]
+    fn urls_for_gitlab_dot_com_work() {
+        // GIVEN
+        let repo = get_repo_on_forge(Some(Forge::GitLab), None, None);
+
+        // WHEN
+        let api_url = repo.api_url();
+        let web_url = repo.web_url();
+
+        // THEN
+        assert_eq!(api_url, "https://gitlab.com/api/v4/projects/owner%2Frepo");
+        assert_eq!(web_url, "https://gitlab.com/owner/repo");
+    }
+
+    #[test]
+    fn urls_for_self_managed_gitlab_with_subgroups_work() {
+        // GIVEN
+        let mut repo = get_repo_on_forge(Some(Forge::GitLab), Some("gitlab.example.com"), None);
+        repo.repo = "subgroup/repo".to_string();
+
+        // WHEN
+        let api_url = repo.api_url();
+        let compare_url = repo.compare_web_url("v1.0.0", "main");
+
+        // THEN
+        assert_eq!(
+            api_url,
+            "https://gitlab.example.com/api/v4/projects/owner%2Fsubgroup%2Frepo"
+        );
+        assert_eq!(
+            compare_url,
+            "https://gitlab.example.com/owner/subgroup/repo/-/compare/v1.0.0...main"
+        );
+    }
+
+    #[test]
+    fn host_for_github_enterprise_server_works() {
+        // GIVEN
+        let repo = get_repo_on_forge(None, Some("github.example.com"), None);
+
+        // WHEN
+        let result = repo.api_url();
+
+        // THEN
+        assert_eq!(result, "https://github.example.com/api/v3/repos/owner/repo");
+    }
+
+    #[test]
+    fn urls_for_gitea_work() {
+        // GIVEN
+        let repo = get_repo_on_forge(Some(Forge::Gitea), Some("git.example.com:3000"), None);
+
+        // WHEN
+        let api_url = repo.api_url();
+        let commits_url = repo.commits_web_url("main");
+        let host = repo.host();
+
+        // THEN
+        assert_eq!(
+            api_url,
+            "https://git.example.com:3000/api/v1/repos/owner/repo"
+        );
+        assert_eq!(
+            commits_url,
+            "https://git.example.com:3000/owner/repo/commits/branch/main"
+        );
+        assert_eq!(host, "git.example.com:3000");
+    }
+}
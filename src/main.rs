@@ -9,16 +9,40 @@ use anyhow::Context;
 use chrono::Utc;
 use clap::Parser;
 use regex::Regex;
+use std::io::IsTerminal;
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
-use crate::domain::{HtmlConfig, OutputFormat, OutputType, RunConfig, StdoutConfig};
+use crate::domain::{
+    CommitLogFetchErrors, DEFAULT_CONCURRENCY, DEFAULT_CONNECT_TIMEOUT_SECS,
+    DEFAULT_REQUEST_TIMEOUT_SECS, FetchConfig, HtmlConfig, MarkdownConfig, OutputFormat,
+    OutputType, RequestTimeouts, RunConfig, StdoutConfig, TemplateConfig,
+};
+
+// exit code used when a report was generated, but commit logs couldn't be fetched for some repos
+const PARTIAL_FAILURE_EXIT_CODE: u8 = 3;
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> anyhow::Result<ExitCode> {
     let args = cli::Args::parse();
 
     if args.debug {
         print!("DEBUG INFO\n{args}");
-        return Ok(());
+        if let cli::UnreleasedCommand::Report {
+            config_file_path,
+            repo_filter,
+            ..
+        } = &args.command
+        {
+            let config = config::get_config_path(config_file_path.clone()).and_then(|path| {
+                let repo_filter_regex = repo_filter.as_deref().map(Regex::new).transpose()?;
+                config::get_from_file(&path, repo_filter_regex.as_ref())
+            });
+            print!("{}", view::render_head_refs(config.ok().as_ref()));
+        }
+        return Ok(ExitCode::SUCCESS);
     }
 
     match args.command {
@@ -30,77 +54,305 @@ async fn main() -> anyhow::Result<()> {
             html_output_path,
             html_title,
             html_template_path,
+            markdown_template_path,
+            template_path,
+            template_output_path,
+            never_released_max_commits,
+            never_released_max_age_days,
+            keep_going,
+            no_cache,
+            cache_dir,
+            github_api,
+            concurrency,
+            request_timeout_secs,
+            connect_timeout_secs,
+            deadline_secs,
+            quiet,
+            progress,
         } => {
-            let repo_filter = repo_filter
-                .map(|pattern| Regex::new(&pattern))
+            // the deadline covers the whole run, including discovering repos from orgs
+            let started_at = Instant::now();
+
+            let repo_filter_regex = repo_filter
+                .as_deref()
+                .map(Regex::new)
                 .transpose()
                 .context("invalid regex pattern provided")?;
 
-            let run_config = RunConfig {
-                output_type: match output_format {
-                    OutputFormat::Stdout => OutputType::Stdout(StdoutConfig {
-                        plain_output: stdout_plain_output,
-                    }),
-                    OutputFormat::Html => {
-                        let template = if let Some(ref template_path) = html_template_path {
-                            Some(std::fs::read_to_string(template_path).with_context(|| {
-                                format!("failed to read HTML template from {:?}", template_path)
-                            })?)
-                        } else {
-                            None
-                        };
-
-                        OutputType::Html(HtmlConfig {
-                            output_path: html_output_path,
-                            title: html_title,
-                            template,
-                        })
-                    }
-                },
+            let output_type = match output_format {
+                OutputFormat::Stdout => OutputType::Stdout(StdoutConfig {
+                    plain_output: stdout_plain_output,
+                }),
+                OutputFormat::Html => {
+                    let template = if let Some(ref template_path) = html_template_path {
+                        Some(std::fs::read_to_string(template_path).with_context(|| {
+                            format!("failed to read HTML template from {:?}", template_path)
+                        })?)
+                    } else {
+                        None
+                    };
+
+                    OutputType::Html(HtmlConfig {
+                        output_path: html_output_path,
+                        title: html_title,
+                        template,
+                    })
+                }
+                OutputFormat::Json => OutputType::Json,
+                OutputFormat::Markdown => {
+                    let template = if let Some(ref template_path) = markdown_template_path {
+                        Some(std::fs::read_to_string(template_path).with_context(|| {
+                            format!("failed to read Markdown template from {:?}", template_path)
+                        })?)
+                    } else {
+                        None
+                    };
+
+                    OutputType::Markdown(MarkdownConfig { template })
+                }
+                OutputFormat::Template => {
+                    // clap ensures that a template path is provided for this output format
+                    let template_path = template_path
+                        .context("a template path is needed for the template output format")?;
+                    let template = std::fs::read_to_string(&template_path).with_context(|| {
+                        format!("failed to read template from {:?}", template_path)
+                    })?;
+
+                    OutputType::Template(TemplateConfig {
+                        template,
+                        output_path: template_output_path,
+                    })
+                }
             };
 
-            let config_path = config_file_path.unwrap_or(config::get_default_config_path()?);
-            let unreleased_config = config::get_from_file(&config_path, repo_filter.as_ref())
-                .with_context(|| {
-                    format!(
-                        "couldn't get config from file \"{}\"",
-                        config_path.to_string_lossy()
-                    )
-                })?;
+            let config_path = config::get_config_path(config_file_path)?;
+            let mut unreleased_config =
+                config::get_from_file(&config_path, repo_filter_regex.as_ref())?;
+
+            let cache = if no_cache {
+                None
+            } else {
+                let cache_dir = get_cache_dir(cache_dir)?;
+                Some(service::ResponseCache::new(cache_dir))
+            };
+            let timeouts = RequestTimeouts {
+                request: Duration::from_secs(
+                    request_timeout_secs
+                        .or(unreleased_config.request_timeout_secs)
+                        .map_or(DEFAULT_REQUEST_TIMEOUT_SECS, NonZeroU64::get),
+                ),
+                connect: Duration::from_secs(
+                    connect_timeout_secs
+                        .or(unreleased_config.connect_timeout_secs)
+                        .map_or(DEFAULT_CONNECT_TIMEOUT_SECS, NonZeroU64::get),
+                ),
+            };
+            let client = service::HttpClient::new(cache, &timeouts)?;
+
+            let deadline_secs = deadline_secs
+                .or(unreleased_config.deadline_secs)
+                .map(NonZeroU64::get);
+            let deadline = deadline_secs.map(|secs| started_at + Duration::from_secs(secs));
+
+            // repos that can't be discovered are reported like the ones whose changelogs can't be
+            // fetched, so that the rest of the report isn't held up by them
+            let mut discovery_errors = CommitLogFetchErrors::new();
+            if !unreleased_config.orgs.is_empty() {
+                let org_credentials = auth::get_org_credentials(&unreleased_config.orgs)?;
+                let discovery = service::discover_repos(
+                    &client,
+                    &unreleased_config.orgs,
+                    &org_credentials,
+                    deadline,
+                )
+                .await;
+                unreleased_config.add_discovered_repos(discovery.repos, repo_filter_regex.as_ref());
+                discovery_errors = discovery.errors;
+            }
 
             if unreleased_config.repos.is_empty() {
+                if !discovery_errors.is_empty() {
+                    return Err(anyhow::anyhow!(discovery_errors));
+                }
                 anyhow::bail!("no repos match the provided filter");
             }
 
-            let token = auth::get_token()?;
+            let fetch_config = FetchConfig {
+                never_released_max_commits,
+                never_released_max_age_days,
+                github_api: github_api
+                    .or(unreleased_config.github_api)
+                    .unwrap_or_default(),
+                concurrency: concurrency
+                    .or(unreleased_config.concurrency)
+                    .map_or(DEFAULT_CONCURRENCY, NonZeroUsize::get),
+                deadline,
+            };
 
-            let changelogs = service::get_changelogs(&unreleased_config.repos, &token).await;
-            if !changelogs.errors.is_empty() {
+            let credentials = auth::get_credentials(&unreleased_config.repos)?;
+
+            // progress is only shown on terminals by default, so that it doesn't end up in logs
+            let progress =
+                service::Progress::new(!quiet && (progress || std::io::stderr().is_terminal()));
+            let mut changelogs = service::get_changelogs(
+                &client,
+                &unreleased_config.repos,
+                &fetch_config,
+                &credentials,
+                &progress,
+            )
+            .await;
+            changelogs.errors.extend(discovery_errors);
+            if !quiet {
+                for quota in client.quotas() {
+                    eprintln!("API quota for {}", quota);
+                }
+            }
+
+            let keep_going = keep_going || unreleased_config.keep_going;
+            if !changelogs.errors.is_empty() && !keep_going {
                 return Err(anyhow::anyhow!(changelogs.errors));
             }
 
-            let output = view::render_output(changelogs.logs.as_slice(), &run_config, Utc::now())?;
+            let run_config = RunConfig {
+                output_type,
+                repo_filter,
+                keep_going,
+                fetch_config,
+                timeouts,
+                deadline_secs,
+            };
+
+            let output = view::render_output(&changelogs, &run_config, Utc::now())?;
 
             match &run_config.output_type {
-                OutputType::Stdout(_) => {
+                OutputType::Stdout(_) | OutputType::Json | OutputType::Markdown(_) => {
                     println!("{}", output);
                 }
                 OutputType::Html(html_config) => {
-                    if let Some(parent) = html_config.output_path.parent() {
-                        std::fs::create_dir_all(parent)
-                            .with_context(|| format!("failed to create directory {:?}", parent))?;
-                    }
-                    std::fs::write(&html_config.output_path, output).with_context(|| {
-                        format!("failed to write HTML to {:?}", html_config.output_path)
-                    })?;
+                    write_output_to_file(&html_config.output_path, output)
+                        .context("failed to write HTML report")?;
                     println!(
                         "HTML report written to: {}",
                         html_config.output_path.display()
                     );
                 }
+                OutputType::Template(template_config) => match &template_config.output_path {
+                    Some(output_path) => {
+                        write_output_to_file(output_path, output)
+                            .context("failed to write report")?;
+                        println!("Report written to: {}", output_path.display());
+                    }
+                    None => print!("{}", output),
+                },
             }
+
+            if !changelogs.errors.is_empty() {
+                eprint!("{}", changelogs.errors);
+                return Ok(ExitCode::from(PARTIAL_FAILURE_EXIT_CODE));
+            }
+        }
+        cli::UnreleasedCommand::Config { command } => run_config_command(command)?,
+        cli::UnreleasedCommand::Cache {
+            command: cli::CacheCommand::Clear { cache_dir },
+        } => {
+            let cache_dir = get_cache_dir(cache_dir)?;
+            if service::clear_cache(&cache_dir)? {
+                println!("Cache cleared: {}", cache_dir.display());
+            } else {
+                println!("Cache is already empty: {}", cache_dir.display());
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn write_output_to_file(path: &Path, output: String) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {:?}", parent))?;
+    }
+    std::fs::write(path, output).with_context(|| format!("failed to write to {:?}", path))?;
+
+    Ok(())
+}
+
+// none of the config commands make network requests
+fn run_config_command(command: cli::ConfigCommand) -> anyhow::Result<()> {
+    match command {
+        cli::ConfigCommand::Init {
+            config_file_path,
+            force,
+        } => {
+            let config_path = config::get_config_path(config_file_path)?;
+            config::write_starter_file(&config_path, force).with_context(|| {
+                format!(
+                    "couldn't write config to file \"{}\"",
+                    config_path.to_string_lossy()
+                )
+            })?;
+            println!("Config written to: {}", config_path.display());
+        }
+        cli::ConfigCommand::Validate { config_file_path } => {
+            let config_path = config::get_config_path(config_file_path)?;
+            let config = config::get_from_file(&config_path, None)?;
+            println!(
+                "config is valid ({} repo(s), {} org(s))",
+                config.repos.len(),
+                config.orgs.len()
+            );
+        }
+        cli::ConfigCommand::Add {
+            repo,
+            head_ref,
+            config_file_path,
+        } => {
+            let config_path = config::get_config_path(config_file_path)?;
+            config::add_repo_to_file(&config_path, &repo, head_ref.as_deref()).with_context(
+                || {
+                    format!(
+                        "couldn't add repo to config file \"{}\"",
+                        config_path.to_string_lossy()
+                    )
+                },
+            )?;
+            println!("Added {} to: {}", repo, config_path.display());
+        }
+        cli::ConfigCommand::Remove {
+            repo,
+            config_file_path,
+        } => {
+            let config_path = config::get_config_path(config_file_path)?;
+            config::remove_repo_from_file(&config_path, &repo).with_context(|| {
+                format!(
+                    "couldn't remove repo from config file \"{}\"",
+                    config_path.to_string_lossy()
+                )
+            })?;
+            println!("Removed {} from: {}", repo, config_path.display());
+        }
+        cli::ConfigCommand::List {
+            config_file_path,
+            repo_filter,
+        } => {
+            let repo_filter_regex = repo_filter
+                .as_deref()
+                .map(Regex::new)
+                .transpose()
+                .context("invalid regex pattern provided")?;
+            let config_path = config::get_config_path(config_file_path)?;
+            let config = config::get_from_file(&config_path, repo_filter_regex.as_ref())?;
+            print!("{}", view::render_repo_list(&config));
         }
     }
 
     Ok(())
 }
+
+fn get_cache_dir(cache_dir: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    match cache_dir {
+        Some(dir) => Ok(dir),
+        None => service::get_default_cache_dir(),
+    }
+}
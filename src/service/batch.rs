@@ -0,0 +1,335 @@
+use super::client::HttpClient;
+use super::github::{self, BaselineSnapshot};
+use super::result::{get_changelog_from_base_ref, get_changelogs_for_repo};
+use super::tag::get_highest_semver_tag;
+use crate::domain::{Credentials, FetchConfig, Forge, GitHubApi, HeadRef, Repo, RepoChangelog};
+use anyhow::Context;
+use futures::future::join_all;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+// GitHub rejects queries that would be too costly to answer; this keeps them well under its limits
+const REPOS_PER_QUERY: usize = 25;
+
+/// GitHub repos whose changelogs are fetched together via GraphQL; they share an API base URL,
+/// and a token (which GitHub's GraphQL API can't be used without)
+#[derive(Debug)]
+pub(super) struct GraphQlBatch {
+    pub(super) api_base_url: String,
+    pub(super) token: String,
+    pub(super) repos: Vec<Repo>,
+}
+
+enum BaseRef {
+    /// None if the repo has never been released
+    Known(Option<String>),
+    /// The lookup didn't fit in a single query
+    Unknown,
+}
+
+enum RestLookup {
+    FromBaseRef(Option<String>),
+    Everything,
+}
+
+/// Splits repos into the batches fetched via GraphQL, and the ones fetched via REST; repos with
+/// packages are always fetched via REST, since they need all of a repo's tags. In auto mode,
+/// GraphQL is only used for hosts with more repos than are fetched in parallel (ie. the
+/// concurrency); fewer repos than that are fetched just as quickly via REST, whose responses can
+/// be cached.
+pub(super) fn get_graphql_batches(
+    repos: &[Repo],
+    github_api: GitHubApi,
+    concurrency: usize,
+    credentials: &Credentials,
+) -> (Vec<GraphQlBatch>, Vec<Repo>) {
+    let mut groups: Vec<GraphQlBatch> = Vec::new();
+    let mut rest_repos = Vec::new();
+
+    for repo in repos {
+        let token = credentials.token_for(repo);
+        let token = match token {
+            Some(token)
+                if github_api != GitHubApi::Rest
+                    && repo.forge == Forge::GitHub
+                    && repo.packages.is_empty() =>
+            {
+                token
+            }
+            _ => {
+                rest_repos.push(repo.clone());
+                continue;
+            }
+        };
+
+        match groups
+            .iter_mut()
+            .find(|group| group.api_base_url == repo.api_base_url && group.token == token)
+        {
+            Some(group) => group.repos.push(repo.clone()),
+            None => groups.push(GraphQlBatch {
+                api_base_url: repo.api_base_url.clone(),
+                token: token.to_string(),
+                repos: vec![repo.clone()],
+            }),
+        }
+    }
+
+    let mut batches = Vec::new();
+    for group in groups {
+        if github_api == GitHubApi::Auto && group.repos.len() <= concurrency {
+            rest_repos.extend(group.repos);
+            continue;
+        }
+
+        for repos in group.repos.chunks(REPOS_PER_QUERY) {
+            batches.push(GraphQlBatch {
+                api_base_url: group.api_base_url.clone(),
+                token: group.token.clone(),
+                repos: repos.to_vec(),
+            });
+        }
+    }
+
+    (batches, rest_repos)
+}
+
+/// Looks up the baselines of all repos in one query, and their commits in another; whatever
+/// doesn't fit in a query (eg. repos with more than 100 tags, or logs with more than 100
+/// commits), and the recent commits of repos that have never been released, is fetched via REST.
+/// The batch's permit is released once its queries are made; each REST lookup then waits for a
+/// permit of its own, so that they count against the concurrency like any other repo.
+pub(super) async fn get_changelogs_for_batch(
+    client: &HttpClient,
+    batch: &GraphQlBatch,
+    fetch_config: &FetchConfig,
+    semaphore: &Semaphore,
+    permit: SemaphorePermit<'_>,
+) -> Vec<(Repo, anyhow::Result<Vec<RepoChangelog>>)> {
+    let mut changelogs = Vec::new();
+    let mut comparisons = Vec::new();
+    let mut rest_lookups = Vec::new();
+
+    let snapshots =
+        match github::get_repo_snapshots(client, &batch.api_base_url, &batch.repos, &batch.token)
+            .await
+        {
+            Ok(snapshots) => snapshots,
+            Err(e) => {
+                return batch
+                    .repos
+                    .iter()
+                    .map(|repo| {
+                        let error = anyhow::anyhow!("{:#}", e)
+                            .context("couldn't look up the repo via GitHub's GraphQL API");
+                        (repo.clone(), Err(error))
+                    })
+                    .collect();
+            }
+        };
+
+    for (repo, snapshot) in batch.repos.iter().zip(snapshots) {
+        let resolved = snapshot
+            .context("couldn't look up the repo via GitHub's GraphQL API")
+            .and_then(|snapshot| resolve_base_ref(repo, snapshot));
+
+        match resolved {
+            Ok((repo, BaseRef::Known(Some(base_ref)))) => comparisons.push((repo, base_ref)),
+            Ok((repo, BaseRef::Known(None))) => {
+                rest_lookups.push((repo, RestLookup::FromBaseRef(None)));
+            }
+            Ok((repo, BaseRef::Unknown)) => rest_lookups.push((repo, RestLookup::Everything)),
+            Err(e) => changelogs.push((repo.clone(), Err(e))),
+        }
+    }
+
+    if !comparisons.is_empty() {
+        match github::get_commit_logs(client, &batch.api_base_url, &comparisons, &batch.token).await
+        {
+            Ok(logs) => {
+                for ((repo, base_ref), log) in comparisons.into_iter().zip(logs) {
+                    match log {
+                        Ok(Some(log)) => {
+                            changelogs.push((repo, Ok(vec![RepoChangelog::Released(log)])));
+                        }
+                        Ok(None) => {
+                            rest_lookups.push((repo, RestLookup::FromBaseRef(Some(base_ref))));
+                        }
+                        Err(e) => changelogs.push((repo, Err(e.context("couldn't fetch commits")))),
+                    }
+                }
+            }
+            Err(e) => {
+                for (repo, _) in comparisons {
+                    let error = anyhow::anyhow!("{:#}", e).context("couldn't fetch commits");
+                    changelogs.push((repo, Err(error)));
+                }
+            }
+        }
+    }
+
+    drop(permit);
+
+    let token = Some(batch.token.as_str());
+    let rest_changelogs = join_all(rest_lookups.into_iter().map(|(repo, lookup)| async move {
+        let permit = semaphore.acquire().await;
+        if let Err(e) = permit {
+            return (
+                repo,
+                Err(anyhow::anyhow!("couldn't acquire semaphore: {e}")),
+            );
+        }
+
+        let result = match lookup {
+            RestLookup::FromBaseRef(base_ref) => {
+                get_changelog_from_base_ref(client, &repo, base_ref.as_deref(), fetch_config, token)
+                    .await
+                    .map(|changelog| vec![changelog])
+            }
+            RestLookup::Everything => {
+                get_changelogs_for_repo(client, &repo, fetch_config, token).await
+            }
+        };
+
+        (repo, result)
+    }))
+    .await;
+    changelogs.extend(rest_changelogs);
+
+    changelogs
+}
+
+// the returned repo has its head ref resolved, like the ones fetched via REST
+fn resolve_base_ref(
+    repo: &Repo,
+    snapshot: github::RepoSnapshot,
+) -> anyhow::Result<(Repo, BaseRef)> {
+    let mut repo = repo.clone();
+    if repo.head_ref == HeadRef::DefaultBranch(None) {
+        let default_branch = snapshot
+            .default_branch
+            .context("repo doesn't have a default branch")
+            .context("couldn't get the repo's default branch")?;
+        repo.head_ref = HeadRef::DefaultBranch(Some(default_branch));
+    }
+
+    let base_ref = match snapshot.baseline {
+        Some(BaselineSnapshot::LatestRelease(release)) => {
+            BaseRef::Known(release.map(|release| release.tag_name))
+        }
+        Some(BaselineSnapshot::Tags(tags)) => BaseRef::Known(
+            get_highest_semver_tag(
+                &tags,
+                None,
+                repo.tag_pattern.as_ref(),
+                repo.consider_prereleases,
+            )
+            .map(|tag| tag.name.clone()),
+        ),
+        None => BaseRef::Unknown,
+    };
+
+    Ok((repo, base_ref))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{RawPackage, RawRepo};
+
+    fn build_repos(count: usize) -> Vec<Repo> {
+        (0..count)
+            .map(|i| build_repo(&format!("owner/repo-{}", i), None))
+            .collect()
+    }
+
+    fn build_repo(name: &str, packages: Option<Vec<RawPackage>>) -> Repo {
+        Repo::try_from(RawRepo {
+            repo: Some(name.to_string()),
+            path: None,
+            forge: None,
+            host: None,
+            head_ref: None,
+            head_refs: None,
+            consider_prereleases: None,
+            api_base_url: None,
+            baseline: None,
+            tag_pattern: None,
+            packages,
+            label: None,
+        })
+        .expect("repo should've been valid")
+    }
+
+    fn get_credentials() -> Credentials {
+        Credentials {
+            github_token: Some("token".to_string()),
+            ..Credentials::default()
+        }
+    }
+
+    #[test]
+    fn auto_mode_only_batches_hosts_with_many_repos() {
+        // GIVEN
+        let few_repos = build_repos(20);
+        let many_repos = build_repos(30);
+
+        // WHEN
+        let (few_batches, few_rest_repos) =
+            get_graphql_batches(&few_repos, GitHubApi::Auto, 20, &get_credentials());
+        let (many_batches, many_rest_repos) =
+            get_graphql_batches(&many_repos, GitHubApi::Auto, 20, &get_credentials());
+
+        // THEN
+        assert!(few_batches.is_empty());
+        assert_eq!(few_rest_repos.len(), 20);
+        assert_eq!(
+            many_batches
+                .iter()
+                .map(|batch| batch.repos.len())
+                .collect::<Vec<_>>(),
+            vec![25, 5]
+        );
+        assert!(many_rest_repos.is_empty());
+    }
+
+    #[test]
+    fn repos_with_packages_are_never_batched() {
+        // GIVEN
+        let package: RawPackage = toml::from_str(
+            r#"
+name = "api"
+tag_prefix = "api/"
+paths = ["api"]
+"#,
+        )
+        .expect("package should've been valid");
+        let repos = vec![
+            build_repo("owner/repo-a", None),
+            build_repo("owner/repo-b", Some(vec![package])),
+        ];
+
+        // WHEN
+        let (batches, rest_repos) =
+            get_graphql_batches(&repos, GitHubApi::Graphql, 20, &get_credentials());
+
+        // THEN
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].repos[0].to_string(), "owner/repo-a");
+        assert_eq!(rest_repos.len(), 1);
+        assert_eq!(rest_repos[0].to_string(), "owner/repo-b");
+    }
+
+    #[test]
+    fn rest_mode_never_batches_repos() {
+        // GIVEN
+        let repos = build_repos(30);
+
+        // WHEN
+        let (batches, rest_repos) =
+            get_graphql_batches(&repos, GitHubApi::Rest, 20, &get_credentials());
+
+        // THEN
+        assert!(batches.is_empty());
+        assert_eq!(rest_repos.len(), 30);
+    }
+}
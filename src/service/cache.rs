@@ -0,0 +1,245 @@
+use anyhow::Context;
+use etcetera::{BaseStrategy, choose_base_strategy};
+use reqwest::header::{
+    AUTHORIZATION, CONTENT_LENGTH, ETAG, HeaderMap, HeaderName, HeaderValue, LAST_MODIFIED,
+};
+use reqwest::{Request, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::hash::{BuildHasher, RandomState};
+use std::path::{Path, PathBuf};
+
+// the header GitLab takes tokens in, besides Authorization
+const PRIVATE_TOKEN: &str = "private-token";
+
+/// Responses stored on disk, so that later runs can make conditional requests for the same URLs;
+/// forges respond to those with a 304 when nothing has changed, which GitHub doesn't count
+/// against the rate limit. Entries are never evicted; they're only removed via [`clear_cache`].
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct CachedResponse {
+    // file names are hashes of keys, so the key is kept around to rule out collisions
+    key: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+pub fn get_default_cache_dir() -> anyhow::Result<PathBuf> {
+    let strategy = choose_base_strategy()
+        .context("couldn't determine your machine's default cache directory")?;
+
+    Ok(strategy.cache_dir().join("unreleased"))
+}
+
+/// Removes the entries written by [`ResponseCache`] (and nothing else, since the directory might
+/// not be one that only holds cached responses), and then the directory itself if that leaves it
+/// empty; returns whether there was anything to remove
+pub fn clear_cache(dir: &Path) -> anyhow::Result<bool> {
+    if !dir.exists() {
+        return Ok(false);
+    }
+
+    let mut removed_any = false;
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("couldn't read directory {:?}", dir))?;
+    for entry in entries {
+        let entry = entry.with_context(|| format!("couldn't read directory {:?}", dir))?;
+        let path = entry.path();
+        if !entry.file_type().is_ok_and(|t| t.is_file()) || !is_cache_entry(&path) {
+            continue;
+        }
+
+        std::fs::remove_file(&path).with_context(|| format!("couldn't remove file {:?}", path))?;
+        removed_any = true;
+    }
+
+    // the directory is left in place if anything else is in it
+    let _ = std::fs::remove_dir(dir);
+
+    Ok(removed_any)
+}
+
+// entries are named "<hash>.json", and are written to "<hash>.<random>.tmp" first
+fn is_cache_entry(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let is_hex = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit());
+
+    match name.split('.').collect::<Vec<_>>().as_slice() {
+        [hash, "json"] => hash.len() == 16 && is_hex(hash),
+        [hash, random, "tmp"] => hash.len() == 16 && is_hex(hash) && is_hex(random),
+        _ => false,
+    }
+}
+
+/// Identifies the response to a request: its URL, along with a hash of the credentials sent with
+/// it, since what a forge responds with depends on who's asking (the token itself doesn't end up
+/// on disk)
+pub(super) fn get_cache_key(request: &Request) -> String {
+    let credentials = [AUTHORIZATION.as_str(), PRIVATE_TOKEN]
+        .into_iter()
+        .filter_map(|name| request.headers().get(name))
+        .flat_map(|value| value.as_bytes().iter().copied().chain([b'\n']))
+        .collect::<Vec<_>>();
+
+    if credentials.is_empty() {
+        return request.url().to_string();
+    }
+
+    format!("{:016x} {}", hash(&credentials), request.url())
+}
+
+// FNV-1a; unlike DefaultHasher, its output doesn't change between Rust versions, so that entries
+// written by one build are found by the next
+fn hash(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+impl ResponseCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    // entries that can't be read are treated as missing; they get overwritten by the next
+    // successful response
+    pub(super) fn get(&self, key: &str) -> Option<CachedResponse> {
+        let contents = std::fs::read_to_string(self.get_entry_path(key)).ok()?;
+        let entry: CachedResponse = serde_json::from_str(&contents).ok()?;
+
+        (entry.key == key).then_some(entry)
+    }
+
+    /// Stores successful responses that can be revalidated later, and hands back an equivalent
+    /// response; failing to write to the cache doesn't fail the request
+    pub(super) async fn store(&self, key: &str, response: Response) -> anyhow::Result<Response> {
+        let headers = response.headers();
+        let etag = get_header(headers, &ETAG);
+        let last_modified = get_header(headers, &LAST_MODIFIED);
+        if response.status() != StatusCode::OK || (etag.is_none() && last_modified.is_none()) {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let headers = headers.clone();
+        let body = response
+            .bytes()
+            .await
+            .context("couldn't read response body")?
+            .to_vec();
+
+        if let Ok(body) = String::from_utf8(body.clone()) {
+            let entry = CachedResponse {
+                key: key.to_string(),
+                etag,
+                last_modified,
+                headers: headers
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_string()))
+                    })
+                    .collect(),
+                body,
+            };
+            let _ = self.write_entry(&entry);
+        }
+
+        Ok(build_response(status, headers, body))
+    }
+
+    fn write_entry(&self, entry: &CachedResponse) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let contents = serde_json::to_string(entry)?;
+
+        // entries are written in full before being moved into place, since several requests can
+        // be made for the same URL at the same time
+        let path = self.get_entry_path(&entry.key);
+        let temp_path = path.with_extension(format!("{:x}.tmp", RandomState::new().hash_one(0)));
+        std::fs::write(&temp_path, contents)?;
+        std::fs::rename(&temp_path, &path)?;
+
+        Ok(())
+    }
+
+    fn get_entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", hash(key.as_bytes())))
+    }
+}
+
+impl CachedResponse {
+    pub(super) fn get_conditional_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = self.etag.as_deref().and_then(|v| v.parse().ok()) {
+            headers.insert(reqwest::header::IF_NONE_MATCH, value);
+        }
+        if let Some(value) = self.last_modified.as_deref().and_then(|v| v.parse().ok()) {
+            headers.insert(reqwest::header::IF_MODIFIED_SINCE, value);
+        }
+
+        headers
+    }
+
+    /// The cached response, with the headers of the "not modified" response it stands in for
+    /// (eg. the current rate limit) taking precedence over the cached ones
+    pub(super) fn into_response(self, not_modified_headers: &HeaderMap) -> Response {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                headers.append(name, value);
+            }
+        }
+        for (name, value) in not_modified_headers {
+            if name != CONTENT_LENGTH {
+                headers.insert(name, value.clone());
+            }
+        }
+
+        build_response(StatusCode::OK, headers, self.body.into_bytes())
+    }
+}
+
+fn get_header(headers: &HeaderMap, name: &HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+fn build_response(status: StatusCode, headers: HeaderMap, body: Vec<u8>) -> Response {
+    let mut response = http::Response::new(body);
+    *response.status_mut() = status;
+    *response.headers_mut() = headers;
+
+    Response::from(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_match_the_fnv_1a_reference_values() {
+        // GIVEN
+        // WHEN
+        let result = [hash(b""), hash(b"a"), hash(b"foobar")];
+
+        // THEN
+        assert_eq!(
+            result,
+            [0xcbf29ce484222325, 0xaf63dc4c8601ec8c, 0x85944171f73967e8]
+        );
+    }
+}
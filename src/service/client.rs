@@ -0,0 +1,381 @@
+use super::cache::{CachedResponse, ResponseCache, get_cache_key};
+use crate::domain::{ApiQuota, RequestTimeouts};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, RequestBuilder, Response, StatusCode, Url};
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const MAX_ATTEMPTS: u32 = 4;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+// rate limits that reset later than this fail the request instead of stalling the run
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(5 * 60);
+
+// a host, and the API on it that a quota applies to
+type QuotaKey = (String, Option<String>);
+
+/// The HTTP client used for all requests made to forges during a run, so that connections are
+/// reused. It waits out rate limits, retries transient failures, keeps track of the API quota
+/// reported by each host, and makes conditional requests for responses it has cached.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    quotas: Arc<Mutex<HashMap<QuotaKey, ApiQuota>>>,
+    cache: Option<ResponseCache>,
+}
+
+impl HttpClient {
+    pub fn new(cache: Option<ResponseCache>, timeouts: &RequestTimeouts) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeouts.request)
+            .connect_timeout(timeouts.connect)
+            .build()
+            .context("failed to build HTTP client")?;
+
+        Ok(Self {
+            client,
+            quotas: Arc::default(),
+            cache,
+        })
+    }
+
+    pub(super) fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub(super) fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    /// Responses that are still unsuccessful after retrying are returned as is
+    pub(super) async fn send(&self, request: RequestBuilder) -> anyhow::Result<Response> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+
+            let (client, request) = request
+                .try_clone()
+                .context("request can't be retried")?
+                .build_split();
+            let mut request = request.context("couldn't build request")?;
+            let cache_key = get_cache_key(&request);
+            let host = get_host(request.url());
+            let resource = get_resource(request.url());
+
+            // responses are cached by URL (and credentials), which only identifies GET requests
+            let cache = self
+                .cache
+                .as_ref()
+                .filter(|_| request.method() == Method::GET);
+            let cached = cache.and_then(|cache| cache.get(&cache_key));
+            if let Some(cached) = &cached {
+                request
+                    .headers_mut()
+                    .extend(cached.get_conditional_headers());
+            }
+
+            if let Some(wait) = self.get_wait_for_exhausted_quota(&host, resource, Utc::now()) {
+                tokio::time::sleep(wait).await;
+            }
+
+            let result = client.execute(request).await;
+            let retry_delay = match &result {
+                Ok(response) => {
+                    self.record_quota(&host, response.headers());
+                    get_retry_delay(response.status(), response.headers(), attempt, Utc::now())
+                }
+                Err(e) if e.is_timeout() || e.is_connect() => Some(get_backoff(attempt)),
+                Err(_) => None,
+            };
+
+            match retry_delay {
+                Some(delay) if attempt < MAX_ATTEMPTS && delay <= MAX_RATE_LIMIT_WAIT => {
+                    tokio::time::sleep(delay).await;
+                }
+                _ => return use_cache(cache, &cache_key, cached, result?).await,
+            }
+        }
+    }
+
+    /// The quota last reported by each host, ordered by host
+    pub fn quotas(&self) -> Vec<ApiQuota> {
+        let mut quotas = self
+            .quotas
+            .lock()
+            .map(|quotas| quotas.values().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        quotas.sort_by(|a, b| (&a.host, &a.resource).cmp(&(&b.host, &b.resource)));
+
+        quotas
+    }
+
+    fn record_quota(&self, host: &str, headers: &HeaderMap) {
+        let Some(quota) = get_quota(host, headers) else {
+            return;
+        };
+
+        if let Ok(mut quotas) = self.quotas.lock() {
+            quotas.insert((quota.host.clone(), quota.resource.clone()), quota);
+        }
+    }
+
+    // requests made once the quota is used up are bound to be rejected, so they're held back
+    // until it resets
+    fn get_wait_for_exhausted_quota(
+        &self,
+        host: &str,
+        resource: &str,
+        now: DateTime<Utc>,
+    ) -> Option<Duration> {
+        let quotas = self.quotas.lock().ok()?;
+
+        quotas
+            .values()
+            .filter(|quota| quota.host == host && quota.remaining == 0)
+            .filter(|quota| quota.resource.as_deref().is_none_or(|r| r == resource))
+            .filter_map(|quota| (quota.resets_at - now).to_std().ok())
+            .filter(|wait| *wait <= MAX_RATE_LIMIT_WAIT)
+            .max()
+    }
+}
+
+async fn use_cache(
+    cache: Option<&ResponseCache>,
+    cache_key: &str,
+    cached: Option<CachedResponse>,
+    response: Response,
+) -> anyhow::Result<Response> {
+    let Some(cache) = cache else {
+        return Ok(response);
+    };
+
+    match cached {
+        Some(cached) if response.status() == StatusCode::NOT_MODIFIED => {
+            Ok(cached.into_response(response.headers()))
+        }
+        _ => cache.store(cache_key, response).await,
+    }
+}
+
+fn get_host(url: &Url) -> String {
+    match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => String::new(),
+    }
+}
+
+// GitHub's GraphQL API has a quota of its own; it reports which one applies to a response in the
+// "x-ratelimit-resource" header, with "core" being the one for its REST API
+fn get_resource(url: &Url) -> &'static str {
+    if url.path().ends_with("/graphql") {
+        "graphql"
+    } else {
+        "core"
+    }
+}
+
+// GitHub prefixes its rate limit headers with "x-", GitLab doesn't
+fn get_rate_limit_header(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers
+        .get(format!("x-ratelimit-{}", name))
+        .or_else(|| headers.get(format!("ratelimit-{}", name)))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+fn get_quota(host: &str, headers: &HeaderMap) -> Option<ApiQuota> {
+    let limit = get_rate_limit_header(headers, "limit")?;
+    let remaining = get_rate_limit_header(headers, "remaining")?;
+    let reset = get_rate_limit_header(headers, "reset")?;
+    let resets_at = DateTime::from_timestamp(i64::try_from(reset).ok()?, 0)?;
+
+    let resource = headers
+        .get("x-ratelimit-resource")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+
+    Some(ApiQuota {
+        host: host.to_string(),
+        resource,
+        limit,
+        remaining,
+        resets_at,
+    })
+}
+
+fn get_retry_delay(
+    status: StatusCode,
+    headers: &HeaderMap,
+    attempt: u32,
+    now: DateTime<Utc>,
+) -> Option<Duration> {
+    let retry_after = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs);
+    let quota_is_exhausted = get_rate_limit_header(headers, "remaining") == Some(0);
+
+    // GitHub responds to rate limited requests with a 403, which is also used for missing
+    // permissions; only the rate limit headers tell them apart
+    let is_rate_limited = status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::FORBIDDEN && (retry_after.is_some() || quota_is_exhausted));
+
+    if is_rate_limited {
+        // secondary rate limits say how long to wait, primary ones say when they reset
+        if let Some(retry_after) = retry_after {
+            return Some(retry_after);
+        }
+
+        if quota_is_exhausted
+            && let Some(reset) = get_rate_limit_header(headers, "reset")
+            && let Some(resets_at) = DateTime::from_timestamp(i64::try_from(reset).ok()?, 0)
+        {
+            let wait = (resets_at - now).to_std().unwrap_or_default();
+            return Some(wait + Duration::from_secs(1));
+        }
+
+        return Some(get_backoff(attempt));
+    }
+
+    match status {
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => Some(get_backoff(attempt)),
+        _ => None,
+    }
+}
+
+// the backoff doubles with every attempt, with up to half of it randomized so that concurrent
+// requests don't retry in lockstep
+fn get_backoff(attempt: u32) -> Duration {
+    let backoff = INITIAL_BACKOFF * 2_u32.pow(attempt.saturating_sub(1));
+    let jitter = (RandomState::new().hash_one(attempt) % 1000) as f64 / 1000.0;
+
+    backoff / 2 + (backoff / 2).mul_f64(jitter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use reqwest::header::HeaderValue;
+
+    fn get_headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn secondary_rate_limits_are_waited_out_for_as_long_as_asked() {
+        // GIVEN
+        let headers = get_headers(&[("retry-after", "30")]);
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = get_retry_delay(StatusCode::FORBIDDEN, &headers, 1, now);
+
+        // THEN
+        assert_eq!(result, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn primary_rate_limits_are_waited_out_until_they_reset() {
+        // GIVEN
+        let headers = get_headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "1737028860"),
+        ]);
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = get_retry_delay(StatusCode::FORBIDDEN, &headers, 1, now);
+
+        // THEN
+        assert_eq!(result, Some(Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn forbidden_responses_without_rate_limit_headers_are_not_retried() {
+        // GIVEN
+        let headers = get_headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "4999"),
+            ("x-ratelimit-reset", "1737028860"),
+        ]);
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = get_retry_delay(StatusCode::FORBIDDEN, &headers, 1, now);
+
+        // THEN
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn server_errors_are_retried_with_a_growing_backoff() {
+        // GIVEN
+        let headers = HeaderMap::new();
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let delays = (1..=3)
+            .map(|attempt| get_retry_delay(StatusCode::BAD_GATEWAY, &headers, attempt, now))
+            .collect::<Vec<_>>();
+
+        // THEN
+        for (delay, max) in delays.into_iter().zip([500, 1000, 2000]) {
+            let delay = delay.expect("delay should've been present");
+            assert!(delay >= Duration::from_millis(max / 2));
+            assert!(delay <= Duration::from_millis(max));
+        }
+    }
+
+    #[test]
+    fn quota_is_read_from_gitlab_rate_limit_headers() {
+        // GIVEN
+        let headers = get_headers(&[
+            ("ratelimit-limit", "2000"),
+            ("ratelimit-remaining", "1990"),
+            ("ratelimit-reset", "1737028860"),
+        ]);
+
+        // WHEN
+        let result = get_quota("gitlab.com", &headers).expect("quota should've been present");
+
+        // THEN
+        assert_eq!(
+            result.to_string(),
+            "gitlab.com: 1990/2000 requests remaining, resets at 2025-01-16 12:01:00 UTC"
+        );
+    }
+
+    #[test]
+    fn quota_names_the_api_it_applies_to_when_reported() {
+        // GIVEN
+        let headers = get_headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "4990"),
+            ("x-ratelimit-reset", "1737028860"),
+            ("x-ratelimit-resource", "graphql"),
+        ]);
+
+        // WHEN
+        let result = get_quota("api.github.com", &headers).expect("quota should've been present");
+
+        // THEN
+        assert_eq!(
+            result.to_string(),
+            "api.github.com (graphql): 4990/5000 requests remaining, resets at 2025-01-16 12:01:00 UTC"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use super::client::HttpClient;
+use super::github::get_org_repos;
+use crate::domain::{CommitLogFetchErrors, Credentials, FetchErrorKind, HeadRef, Org, Repo};
+use std::time::Instant;
+
+/// The repos discovered from orgs, and the errors for the orgs whose repos couldn't be listed
+#[derive(Debug)]
+pub struct Discovery {
+    pub repos: Vec<Repo>,
+    pub errors: CommitLogFetchErrors,
+}
+
+/// Lists the repos of the provided orgs that pass their filters; an org that can't be listed (or
+/// isn't listed before the deadline) doesn't keep the others from being discovered
+pub async fn discover_repos(
+    client: &HttpClient,
+    orgs: &[Org],
+    credentials: &Credentials,
+    deadline: Option<Instant>,
+) -> Discovery {
+    let mut repos = Vec::new();
+    let mut errors = CommitLogFetchErrors::new();
+
+    for org in orgs {
+        let listing = get_org_repos(client, org, credentials.token_for_org(org));
+        let result = match deadline {
+            Some(deadline) => {
+                tokio::time::timeout_at(tokio::time::Instant::from_std(deadline), listing)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(anyhow::anyhow!(
+                            "timed out; the deadline passed before its repos were listed"
+                        ))
+                    })
+            }
+            None => listing.await,
+        };
+        let discovered = match result {
+            Ok(discovered) => discovered,
+            Err(error) => {
+                errors.add_system_error(
+                    FetchErrorKind::Discovery,
+                    error.context(format!("couldn't discover repos for \"{}\"", org.name)),
+                );
+                continue;
+            }
+        };
+
+        for discovered_repo in discovered.iter().filter(|r| org.includes(r)) {
+            let mut repo = match Repo::try_from(org.get_raw_repo(discovered_repo)) {
+                Ok(repo) => repo,
+                Err(e) => {
+                    errors.add_system_error(
+                        FetchErrorKind::Discovery,
+                        anyhow::anyhow!(
+                            "discovered repo \"{}\" is invalid:\n{}",
+                            discovered_repo.full_name,
+                            e
+                        ),
+                    );
+                    continue;
+                }
+            };
+            // the default branch is listed along with the repo, so it needn't be looked up later
+            if repo.head_ref == HeadRef::DefaultBranch(None) {
+                repo.head_ref =
+                    HeadRef::DefaultBranch(Some(discovered_repo.default_branch.clone()));
+            }
+            repos.push(repo);
+        }
+    }
+
+    Discovery { repos, errors }
+}
@@ -1,55 +1,187 @@
-use super::result::get_changelog_for_repo;
-use crate::domain::{CommitLogFetchErrors, CommitLogResults, Repo};
+use super::batch::{get_changelogs_for_batch, get_graphql_batches};
+use super::client::HttpClient;
+use super::progress::Progress;
+use super::result::get_changelogs_for_repo;
+use crate::domain::{
+    CommitLogFetchErrors, CommitLogResults, Credentials, FetchConfig, FetchErrorKind, Repo,
+    RepoChangelog,
+};
 use futures::stream::{FuturesUnordered, StreamExt};
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 
-const MAX_CONCURRENT_FETCHES: usize = 20;
+pub async fn get_changelogs(
+    client: &HttpClient,
+    repos: &[Repo],
+    fetch_config: &FetchConfig,
+    credentials: &Credentials,
+    progress: &Progress,
+) -> CommitLogResults {
+    let semaphore = Arc::new(Semaphore::new(fetch_config.concurrency));
+    let mut tasks = Vec::new();
+    progress.set_total(repos.len());
 
-pub async fn get_changelogs(repos: &[Repo], token: &str) -> CommitLogResults {
-    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES));
-    let mut futures = FuturesUnordered::new();
+    let (batches, rest_repos) = get_graphql_batches(
+        repos,
+        fetch_config.github_api,
+        fetch_config.concurrency,
+        credentials,
+    );
+
+    // a batch takes up a single slot while its queries are made (one after the other); the REST
+    // lookups it falls back to take up slots of their own
+    for batch in batches {
+        let semaphore = Arc::clone(&semaphore);
+        let client = client.clone();
+        let fetch_config = *fetch_config;
+        let batch_repos = batch.repos.clone();
+        let progress = progress.clone();
+
+        let handle = tokio::task::spawn(async move {
+            let permit = match semaphore.acquire().await {
+                Ok(permit) => permit,
+                Err(e) => {
+                    return batch
+                        .repos
+                        .into_iter()
+                        .map(|repo| {
+                            (
+                                repo,
+                                Err(anyhow::anyhow!("couldn't acquire semaphore: {e}")),
+                            )
+                        })
+                        .collect();
+                }
+            };
+
+            progress.start(&batch.repos);
+            get_changelogs_for_batch(&client, &batch, &fetch_config, &semaphore, permit).await
+        });
+        tasks.push((batch_repos, handle));
+    }
 
-    for repo in repos {
+    for repo in rest_repos {
         let semaphore = Arc::clone(&semaphore);
-        let repo_clone = repo.clone();
-        let token_clone = token.to_string();
+        let client = client.clone();
+        let fetch_config = *fetch_config;
+        let token = credentials.token_for(&repo).map(|t| t.to_string());
+        let task_repos = vec![repo.clone()];
+        let progress = progress.clone();
 
-        futures.push(tokio::task::spawn(async move {
+        let handle = tokio::task::spawn(async move {
             let permit = semaphore.acquire().await;
             if let Err(e) = permit {
-                return (
-                    repo_clone,
+                return vec![(
+                    repo,
                     Err(anyhow::anyhow!("couldn't acquire semaphore: {e}")),
-                );
+                )];
             }
 
-            let result = get_changelog_for_repo(&repo_clone, &token_clone).await;
+            progress.start(std::slice::from_ref(&repo));
+            let result =
+                get_changelogs_for_repo(&client, &repo, &fetch_config, token.as_deref()).await;
+
+            vec![(repo, result)]
+        });
+        tasks.push((task_repos, handle));
+    }
 
-            (repo_clone, result)
-        }));
+    // the repos of each task are kept around until it finishes, so that the ones still being
+    // fetched when the deadline passes can be reported
+    let mut pending_repos = Vec::new();
+    let mut abort_handles = Vec::new();
+    let mut futures = FuturesUnordered::new();
+    for (i, (task_repos, handle)) in tasks.into_iter().enumerate() {
+        pending_repos.push(Some(task_repos));
+        abort_handles.push(handle.abort_handle());
+        futures.push(async move { (i, handle.await) });
     }
 
     let mut commit_logs = Vec::new();
+    let mut never_released_logs = Vec::new();
     let mut errors = CommitLogFetchErrors::new();
 
-    while let Some(task_result) = futures.next().await {
-        match task_result {
-            Ok((_app, Ok(Some(log)))) => commit_logs.push(log),
-            Ok((app, Err(e))) => {
-                errors.add_repo_error(app, e);
-            }
+    let deadline = fetch_config.deadline.map(tokio::time::Instant::from_std);
+    loop {
+        let next = match deadline {
+            Some(deadline) => match tokio::time::timeout_at(deadline, futures.next()).await {
+                Ok(next) => next,
+                Err(_) => break,
+            },
+            None => futures.next().await,
+        };
+        let Some((i, task_result)) = next else {
+            break;
+        };
+        let task_repos = pending_repos[i].take().unwrap_or_default();
+
+        let results = match task_result {
+            Ok(results) => results,
             Err(e) => {
-                errors.add_system_error(anyhow::anyhow!("task panicked: {e}"));
+                errors.add_system_error(
+                    FetchErrorKind::Internal,
+                    anyhow::anyhow!("task panicked: {e}"),
+                );
+                progress.finish(&task_repos);
+                continue;
+            }
+        };
+
+        for result in results {
+            match result {
+                (_app, Ok(changelogs)) => {
+                    for changelog in changelogs {
+                        match changelog {
+                            RepoChangelog::Released(log) => commit_logs.push(log),
+                            RepoChangelog::NeverReleased(log) => never_released_logs.push(log),
+                        }
+                    }
+                }
+                (app, Err(e)) => {
+                    progress.report_failure(&app, &e);
+                    errors.add_repo_error(app, get_error_kind(&e), e);
+                }
             }
-            _ => {}
         }
+        progress.finish(&task_repos);
     }
 
-    commit_logs.sort_by(|a, b| a.repo.cmp(&b.repo));
+    for (task_repos, abort_handle) in pending_repos.into_iter().zip(abort_handles) {
+        let Some(task_repos) = task_repos else {
+            continue;
+        };
+
+        abort_handle.abort();
+        for repo in task_repos {
+            let error =
+                anyhow::anyhow!("timed out; the deadline passed before its changelog was fetched");
+            progress.report_failure(&repo, &error);
+            errors.add_repo_error(repo, FetchErrorKind::Deadline, error);
+        }
+    }
+    progress.clear();
+
+    // views section the report by group; ungrouped repos come first
+    commit_logs.sort_by(|a, b| (&a.repo.group, &a.repo).cmp(&(&b.repo.group, &b.repo)));
+    never_released_logs.sort_by(|a, b| (&a.repo.group, &a.repo).cmp(&(&b.repo.group, &b.repo)));
 
     CommitLogResults {
         logs: commit_logs,
+        never_released_logs,
         errors,
     }
 }
+
+fn get_error_kind(error: &anyhow::Error) -> FetchErrorKind {
+    let timed_out = error.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(reqwest::Error::is_timeout)
+    });
+
+    if timed_out {
+        FetchErrorKind::Timeout
+    } else {
+        FetchErrorKind::Fetch
+    }
+}
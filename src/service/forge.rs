@@ -0,0 +1,108 @@
+use super::client::HttpClient;
+use super::{gitea, github, gitlab, local};
+use crate::domain::{Commit, CommitLog, Forge, LastRelease, Repo, Tag};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+// the functions here dispatch to the API client for the forge hosting the repo; every forge is
+// expected to return results in the same shape (eg. commits are always ordered newest first)
+
+pub(super) const MAX_COMMITS_PER_LOG: usize = 1000;
+
+pub(super) async fn get_latest_release(
+    client: &HttpClient,
+    repo: &Repo,
+    token: Option<&str>,
+) -> anyhow::Result<Option<LastRelease>> {
+    match repo.forge {
+        Forge::GitHub => {
+            github::get_latest_release(client, repo, repo.consider_prereleases, token).await
+        }
+        Forge::GitLab => {
+            gitlab::get_latest_release(client, repo, repo.consider_prereleases, token).await
+        }
+        Forge::Gitea => {
+            gitea::get_latest_release(client, repo, repo.consider_prereleases, token).await
+        }
+        // config validation ensures that local repos always use tags as their baseline
+        Forge::Local => Err(anyhow::anyhow!("local repos don't have releases")),
+    }
+}
+
+pub(super) async fn get_default_branch(
+    client: &HttpClient,
+    repo: &Repo,
+    token: Option<&str>,
+) -> anyhow::Result<String> {
+    match repo.forge {
+        Forge::GitHub => github::get_default_branch(client, repo, token).await,
+        Forge::GitLab => gitlab::get_default_branch(client, repo, token).await,
+        Forge::Gitea => gitea::get_default_branch(client, repo, token).await,
+        Forge::Local => local::get_default_branch(repo).await,
+    }
+}
+
+pub(super) async fn get_tags(
+    client: &HttpClient,
+    repo: &Repo,
+    token: Option<&str>,
+) -> anyhow::Result<Vec<Tag>> {
+    match repo.forge {
+        Forge::GitHub => github::get_tags(client, repo, token).await,
+        Forge::GitLab => gitlab::get_tags(client, repo, token).await,
+        Forge::Gitea => gitea::get_tags(client, repo, token).await,
+        Forge::Local => local::get_tags(repo).await,
+    }
+}
+
+pub(super) async fn get_commit_log(
+    client: &HttpClient,
+    repo: &Repo,
+    base_ref: &str,
+    head_ref: &str,
+    token: Option<&str>,
+) -> anyhow::Result<CommitLog> {
+    match repo.forge {
+        Forge::GitHub => github::get_commit_log(client, repo, base_ref, head_ref, token).await,
+        Forge::GitLab => gitlab::get_commit_log(client, repo, base_ref, head_ref, token).await,
+        Forge::Gitea => gitea::get_commit_log(client, repo, base_ref, head_ref, token).await,
+        Forge::Local => local::get_commit_log(repo, base_ref, head_ref).await,
+    }
+}
+
+pub(super) async fn get_recent_commits(
+    client: &HttpClient,
+    repo: &Repo,
+    path: Option<&str>,
+    max_commits: usize,
+    since: Option<DateTime<Utc>>,
+    token: Option<&str>,
+) -> anyhow::Result<Vec<Commit>> {
+    match repo.forge {
+        Forge::GitHub => {
+            github::get_recent_commits(client, repo, path, max_commits, since, token).await
+        }
+        Forge::GitLab => {
+            gitlab::get_recent_commits(client, repo, path, max_commits, since, token).await
+        }
+        Forge::Gitea => {
+            gitea::get_recent_commits(client, repo, path, max_commits, since, token).await
+        }
+        Forge::Local => local::get_recent_commits(repo, path, max_commits, since).await,
+    }
+}
+
+pub(super) async fn get_shas_touching_paths(
+    client: &HttpClient,
+    repo: &Repo,
+    paths: &[String],
+    since: DateTime<Utc>,
+    token: Option<&str>,
+) -> anyhow::Result<HashSet<String>> {
+    match repo.forge {
+        Forge::GitHub => github::get_shas_touching_paths(client, repo, paths, since, token).await,
+        Forge::GitLab => gitlab::get_shas_touching_paths(client, repo, paths, since, token).await,
+        Forge::Gitea => gitea::get_shas_touching_paths(client, repo, paths, since, token).await,
+        Forge::Local => local::get_shas_touching_paths(repo, paths, since).await,
+    }
+}
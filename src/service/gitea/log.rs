@@ -0,0 +1,136 @@
+use super::super::client::HttpClient;
+use super::super::forge::MAX_COMMITS_PER_LOG;
+use super::super::pagination::get_link_from_headers;
+use super::request::get;
+use crate::domain::{Commit, CommitLog, Repo, encode_ref};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+// Gitea caps page sizes at 50 by default
+const COMMITS_PER_PAGE: usize = 50;
+
+#[derive(Debug, Deserialize)]
+struct CommitSha {
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompareResponse {
+    commits: Vec<Commit>,
+    total_commits: usize,
+}
+
+pub(crate) async fn get_commit_log(
+    client: &HttpClient,
+    repo: &Repo,
+    base_ref: &str,
+    head_ref: &str,
+    token: Option<&str>,
+) -> anyhow::Result<CommitLog> {
+    let url = format!(
+        "{}/compare/{}...{}",
+        repo.api_url(),
+        encode_ref(base_ref),
+        encode_ref(head_ref)
+    );
+
+    let (compare, _) = get::<CompareResponse>(client, &url, token).await?;
+
+    // Gitea returns all commits newest first in one go, and doesn't report how far behind the base
+    // ref the head ref is
+    let mut commits = compare.commits;
+    commits.truncate(MAX_COMMITS_PER_LOG);
+
+    Ok(CommitLog {
+        repo: repo.clone(),
+        package: None,
+        base_ref: base_ref.to_string(),
+        head_ref: head_ref.to_string(),
+        commits,
+        html_url: repo.compare_web_url(base_ref, head_ref),
+        total_commits: compare.total_commits,
+        ahead_by: compare.total_commits,
+        behind_by: 0,
+        total_is_lower_bound: false,
+    })
+}
+
+pub(crate) async fn get_recent_commits(
+    client: &HttpClient,
+    repo: &Repo,
+    path: Option<&str>,
+    max_commits: usize,
+    since: Option<DateTime<Utc>>,
+    token: Option<&str>,
+) -> anyhow::Result<Vec<Commit>> {
+    let url = get_commits_url(repo, path, since, max_commits.min(COMMITS_PER_PAGE))?;
+
+    let mut commits = Vec::new();
+    let mut next_url = Some(url.to_string());
+    while let Some(url) = next_url
+        && commits.len() < max_commits
+    {
+        let (page, headers): (Vec<Commit>, _) = get(client, &url, token).await?;
+        commits.extend(page);
+        next_url = get_link_from_headers(&headers, "next");
+    }
+
+    commits.truncate(max_commits);
+
+    Ok(commits)
+}
+
+pub(crate) async fn get_shas_touching_paths(
+    client: &HttpClient,
+    repo: &Repo,
+    paths: &[String],
+    since: DateTime<Utc>,
+    token: Option<&str>,
+) -> anyhow::Result<HashSet<String>> {
+    let mut shas = HashSet::new();
+
+    for path in paths {
+        let url = get_commits_url(repo, Some(path), Some(since), COMMITS_PER_PAGE)?;
+
+        let mut next_url = Some(url.to_string());
+        while let Some(url) = next_url {
+            let (page, headers): (Vec<CommitSha>, _) = get(client, &url, token).await?;
+            shas.extend(page.into_iter().map(|c| c.sha));
+            next_url = get_link_from_headers(&headers, "next");
+        }
+    }
+
+    Ok(shas)
+}
+
+fn get_commits_url(
+    repo: &Repo,
+    path: Option<&str>,
+    since: Option<DateTime<Utc>>,
+    limit: usize,
+) -> anyhow::Result<Url> {
+    let mut url = Url::parse(&format!("{}/commits", repo.api_url()))
+        .context("couldn't build URL for Gitea API")?;
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("sha", repo.head_ref.name())
+            .append_pair("limit", &limit.to_string());
+        // skip the parts of the response that are expensive for Gitea to compute
+        query
+            .append_pair("stat", "false")
+            .append_pair("verification", "false")
+            .append_pair("files", "false");
+        if let Some(path) = path {
+            query.append_pair("path", path);
+        }
+        if let Some(since) = since {
+            query.append_pair("since", &since.to_rfc3339());
+        }
+    }
+
+    Ok(url)
+}
@@ -0,0 +1,10 @@
+mod log;
+mod release;
+mod repo;
+mod request;
+mod tag;
+
+pub(super) use log::{get_commit_log, get_recent_commits, get_shas_touching_paths};
+pub(super) use release::get_latest_release;
+pub(super) use repo::get_default_branch;
+pub(super) use tag::get_tags;
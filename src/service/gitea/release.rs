@@ -0,0 +1,48 @@
+use super::super::client::HttpClient;
+use super::super::pagination::get_link_from_headers;
+use super::request::get;
+use crate::domain::{LastRelease, Repo};
+use serde::Deserialize;
+
+// Gitea caps page sizes at 50 by default
+const RELEASES_PER_PAGE: usize = 50;
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    draft: bool,
+    prerelease: bool,
+}
+
+// Gitea doesn't have a "latest release" endpoint that skips prereleases, so releases are listed
+// (newest first) until a suitable one is found
+pub(crate) async fn get_latest_release(
+    client: &HttpClient,
+    repo: &Repo,
+    consider_prereleases: bool,
+    token: Option<&str>,
+) -> anyhow::Result<Option<LastRelease>> {
+    let mut next_url = Some(format!(
+        "{}/releases?limit={}",
+        repo.api_url(),
+        RELEASES_PER_PAGE
+    ));
+
+    while let Some(url) = next_url {
+        let (releases, headers): (Vec<Release>, _) = get(client, &url, token).await?;
+
+        if let Some(release) = releases
+            .into_iter()
+            .find(|r| !r.draft && (consider_prereleases || !r.prerelease))
+        {
+            return Ok(Some(LastRelease {
+                tag_name: release.tag_name,
+                draft: false,
+            }));
+        }
+
+        next_url = get_link_from_headers(&headers, "next");
+    }
+
+    Ok(None)
+}
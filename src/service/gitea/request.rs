@@ -0,0 +1,56 @@
+use super::super::client::HttpClient;
+use anyhow::Context;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+pub(super) async fn send_request(
+    client: &HttpClient,
+    url: &str,
+    token: Option<&str>,
+) -> anyhow::Result<reqwest::Response> {
+    let mut request = client
+        .get(url)
+        .header("Accept", "application/json")
+        .header("User-Agent", "unreleased@v0.1.0");
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("token {}", token));
+    }
+
+    client
+        .send(request)
+        .await
+        .context("failed to send request to Gitea API")
+}
+
+pub(super) async fn parse_response<T>(response: reqwest::Response) -> anyhow::Result<(T, HeaderMap)>
+where
+    T: DeserializeOwned,
+{
+    let status = response.status();
+    if !status.is_success() {
+        match response.text().await {
+            Ok(body) => anyhow::bail!("Gitea API request failed with status {}: {}", status, body),
+            Err(_) => anyhow::bail!("Gitea API request failed with status {}", status,),
+        }
+    }
+
+    let headers = response.headers().clone();
+    let body: T = response
+        .json()
+        .await
+        .context("failed to parse Gitea API response")?;
+
+    Ok((body, headers))
+}
+
+pub(super) async fn get<T>(
+    client: &HttpClient,
+    url: &str,
+    token: Option<&str>,
+) -> anyhow::Result<(T, HeaderMap)>
+where
+    T: DeserializeOwned,
+{
+    let response = send_request(client, url, token).await?;
+    parse_response(response).await
+}
@@ -0,0 +1,411 @@
+use super::super::client::HttpClient;
+use super::request::post_graphql;
+use crate::domain::{Author, Baseline, Commit, CommitDetail, CommitLog, LastRelease, Repo, Tag};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+// GitHub caps connections at 100 nodes; lists that don't fit in one are left to the REST API,
+// which pages through them
+const NODES_PER_CONNECTION: usize = 100;
+
+/// What a batched query found out about a repo
+#[derive(Debug)]
+pub(crate) struct RepoSnapshot {
+    pub(crate) default_branch: Option<String>,
+    /// None if the baseline couldn't be looked up in a single query
+    pub(crate) baseline: Option<BaselineSnapshot>,
+}
+
+#[derive(Debug)]
+pub(crate) enum BaselineSnapshot {
+    LatestRelease(Option<LastRelease>),
+    Tags(Vec<Tag>),
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryResponse<T> {
+    data: Option<HashMap<String, Option<T>>>,
+    #[serde(default)]
+    errors: Vec<QueryError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryError {
+    message: String,
+    #[serde(default)]
+    path: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RepoNode {
+    default_branch_ref: Option<RefNode>,
+    latest_release: Option<ReleaseNode>,
+    releases: Option<Connection<ReleaseNode>>,
+    refs: Option<Connection<RefNode>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RefNode {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseNode {
+    tag_name: String,
+    is_draft: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Connection<T> {
+    nodes: Vec<T>,
+    page_info: PageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComparisonRepoNode {
+    #[serde(rename = "ref")]
+    base_ref: Option<BaseRefNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BaseRefNode {
+    compare: Option<ComparisonNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ComparisonNode {
+    ahead_by: usize,
+    behind_by: usize,
+    commits: CommitConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommitConnection {
+    total_count: usize,
+    nodes: Vec<CommitNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommitNode {
+    oid: String,
+    url: String,
+    message: String,
+    committed_date: DateTime<Utc>,
+    author: Option<GitActorNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitActorNode {
+    name: Option<String>,
+    date: Option<DateTime<Utc>>,
+}
+
+/// Looks up the default branch and baseline of every repo in a single query; all repos are
+/// expected to be hosted at the same API base URL
+pub(crate) async fn get_repo_snapshots(
+    client: &HttpClient,
+    api_base_url: &str,
+    repos: &[Repo],
+    token: &str,
+) -> anyhow::Result<Vec<anyhow::Result<RepoSnapshot>>> {
+    let mut query = Query::new();
+    for repo in repos {
+        let owner = query.add_variable("owner", &repo.owner);
+        let name = query.add_variable("name", &repo.repo);
+        query.add_field(format!(
+            "repository(owner: ${}, name: ${}) {{ defaultBranchRef {{ name }} {} }}",
+            owner,
+            name,
+            get_baseline_fields(repo)
+        ));
+    }
+
+    let nodes = query.run::<RepoNode>(client, api_base_url, token).await?;
+
+    Ok(repos
+        .iter()
+        .zip(nodes)
+        .map(|(repo, node)| node.map(|node| get_snapshot(repo, node)))
+        .collect())
+}
+
+/// Fetches the commits between each base ref and the head ref of its repo in a single query;
+/// logs with more commits than fit in one query come back as None
+pub(crate) async fn get_commit_logs(
+    client: &HttpClient,
+    api_base_url: &str,
+    comparisons: &[(Repo, String)],
+    token: &str,
+) -> anyhow::Result<Vec<anyhow::Result<Option<CommitLog>>>> {
+    let mut query = Query::new();
+    for (repo, base_ref) in comparisons {
+        let owner = query.add_variable("owner", &repo.owner);
+        let name = query.add_variable("name", &repo.repo);
+        let base = query.add_variable("base", &format!("refs/tags/{}", base_ref));
+        let head = query.add_variable("head", repo.head_ref.name());
+        query.add_field(format!(
+            "repository(owner: ${}, name: ${}) {{ ref(qualifiedName: ${}) {{ compare(headRef: ${}) {{ aheadBy behindBy commits(last: {}) {{ totalCount nodes {{ oid url message committedDate author {{ name date }} }} }} }} }} }}",
+            owner, name, base, head, NODES_PER_CONNECTION
+        ));
+    }
+
+    let nodes = query
+        .run::<ComparisonRepoNode>(client, api_base_url, token)
+        .await?;
+
+    Ok(comparisons
+        .iter()
+        .zip(nodes)
+        .map(|((repo, base_ref), node)| get_commit_log(repo, base_ref, node?))
+        .collect())
+}
+
+/// A query made up of one aliased field per repo; values are passed as variables, so that
+/// they don't need to be escaped
+struct Query {
+    fields: Vec<String>,
+    parameters: Vec<String>,
+    variables: Map<String, Value>,
+}
+
+impl Query {
+    fn new() -> Self {
+        Self {
+            fields: Vec::new(),
+            parameters: Vec::new(),
+            variables: Map::new(),
+        }
+    }
+
+    // variables are named after the field they're used in
+    fn add_variable(&mut self, prefix: &str, value: &str) -> String {
+        let name = format!("{}{}", prefix, self.fields.len());
+        self.parameters.push(format!("${}: String!", name));
+        self.variables
+            .insert(name.clone(), Value::String(value.to_string()));
+
+        name
+    }
+
+    fn add_field(&mut self, field: String) {
+        self.fields
+            .push(format!("r{}: {}", self.fields.len(), field));
+    }
+
+    /// Results are in the order the fields were added in; a field that GitHub reports an error
+    /// for (eg. a repo that doesn't exist) fails on its own, without failing the others
+    async fn run<T>(
+        self,
+        client: &HttpClient,
+        api_base_url: &str,
+        token: &str,
+    ) -> anyhow::Result<Vec<anyhow::Result<T>>>
+    where
+        T: DeserializeOwned,
+    {
+        let body = serde_json::json!({
+            "query": format!("query({}) {{ {} }}", self.parameters.join(", "), self.fields.join(" ")),
+            "variables": self.variables,
+        });
+
+        let response: QueryResponse<T> =
+            post_graphql(client, &get_graphql_url(api_base_url), &body, token).await?;
+
+        let Some(mut data) = response.data else {
+            let messages = response
+                .errors
+                .iter()
+                .map(|e| e.message.as_str())
+                .collect::<Vec<_>>();
+            anyhow::bail!(
+                "GitHub's GraphQL API returned errors: {}",
+                messages.join("; ")
+            );
+        };
+
+        Ok((0..self.fields.len())
+            .map(|i| {
+                let alias = format!("r{}", i);
+                let error = response
+                    .errors
+                    .iter()
+                    .find(|e| e.path.first().and_then(Value::as_str) == Some(alias.as_str()));
+                if let Some(error) = error {
+                    anyhow::bail!("{}", error.message);
+                }
+
+                data.remove(&alias)
+                    .flatten()
+                    .ok_or_else(|| anyhow::anyhow!("GitHub's GraphQL API didn't return any data"))
+            })
+            .collect())
+    }
+}
+
+// GitHub Enterprise Server serves its GraphQL API at /api/graphql, next to the REST API at
+// /api/v3
+fn get_graphql_url(api_base_url: &str) -> String {
+    match api_base_url.strip_suffix("/v3") {
+        Some(base_url) => format!("{}/graphql", base_url),
+        None => format!("{}/graphql", api_base_url),
+    }
+}
+
+// only the lookup the repo's baseline needs is made; these mirror the ones made via REST
+fn get_baseline_fields(repo: &Repo) -> String {
+    match (repo.baseline, repo.consider_prereleases) {
+        (Baseline::Release, false) => "latestRelease { tagName isDraft }".to_string(),
+        (Baseline::Release, true) => format!(
+            "releases(first: {}, orderBy: {{field: CREATED_AT, direction: DESC}}) {{ nodes {{ tagName isDraft }} pageInfo {{ hasNextPage }} }}",
+            NODES_PER_CONNECTION
+        ),
+        (Baseline::Tag, _) => format!(
+            "refs(refPrefix: \"refs/tags/\", first: {}) {{ nodes {{ name }} pageInfo {{ hasNextPage }} }}",
+            NODES_PER_CONNECTION
+        ),
+    }
+}
+
+fn get_snapshot(repo: &Repo, node: RepoNode) -> RepoSnapshot {
+    let baseline = match (repo.baseline, repo.consider_prereleases) {
+        (Baseline::Release, false) => Some(BaselineSnapshot::LatestRelease(
+            node.latest_release.map(LastRelease::from),
+        )),
+        (Baseline::Release, true) => node.releases.and_then(|releases| {
+            match releases.nodes.into_iter().find(|r| !r.is_draft) {
+                Some(release) => Some(BaselineSnapshot::LatestRelease(Some(release.into()))),
+                None if releases.page_info.has_next_page => None,
+                None => Some(BaselineSnapshot::LatestRelease(None)),
+            }
+        }),
+        (Baseline::Tag, _) => node
+            .refs
+            .filter(|refs| !refs.page_info.has_next_page)
+            .map(|refs| {
+                BaselineSnapshot::Tags(
+                    refs.nodes
+                        .into_iter()
+                        .map(|r| Tag { name: r.name })
+                        .collect(),
+                )
+            }),
+    };
+
+    RepoSnapshot {
+        default_branch: node.default_branch_ref.map(|r| r.name),
+        baseline,
+    }
+}
+
+fn get_commit_log(
+    repo: &Repo,
+    base_ref: &str,
+    node: ComparisonRepoNode,
+) -> anyhow::Result<Option<CommitLog>> {
+    let head_ref = repo.head_ref.name();
+    let comparison = node
+        .base_ref
+        .ok_or_else(|| anyhow::anyhow!("tag \"{}\" doesn't exist", base_ref))?
+        .compare
+        .ok_or_else(|| anyhow::anyhow!("couldn't compare {}...{}", base_ref, head_ref))?;
+
+    let total_commits = comparison.commits.total_count;
+    if comparison.commits.nodes.len() < total_commits {
+        return Ok(None);
+    }
+
+    // commits are listed in chronological order
+    let commits = comparison
+        .commits
+        .nodes
+        .into_iter()
+        .rev()
+        .map(Commit::from)
+        .collect();
+
+    Ok(Some(CommitLog {
+        repo: repo.clone(),
+        package: None,
+        base_ref: base_ref.to_string(),
+        head_ref: head_ref.to_string(),
+        commits,
+        html_url: repo.compare_web_url(base_ref, head_ref),
+        total_commits,
+        ahead_by: comparison.ahead_by,
+        behind_by: comparison.behind_by,
+        total_is_lower_bound: false,
+    }))
+}
+
+impl From<ReleaseNode> for LastRelease {
+    fn from(node: ReleaseNode) -> Self {
+        Self {
+            tag_name: node.tag_name,
+            draft: node.is_draft,
+        }
+    }
+}
+
+// the author's date is the one shown for commits fetched via REST; the commit date stands in
+// for it when git doesn't have one
+impl From<CommitNode> for Commit {
+    fn from(node: CommitNode) -> Self {
+        let (name, date) = match node.author {
+            Some(author) => (
+                author.name.unwrap_or_default(),
+                author.date.unwrap_or(node.committed_date),
+            ),
+            None => (String::new(), node.committed_date),
+        };
+
+        Self {
+            sha: node.oid,
+            commit: CommitDetail {
+                message: node.message,
+                author: Author { name, date },
+            },
+            html_url: node.url,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graphql_url_is_derived_from_the_rest_api_base_url() {
+        // GIVEN
+        let api_base_urls = [
+            "https://api.github.com",
+            "https://github.example.com/api/v3",
+        ];
+
+        // WHEN
+        let result = api_base_urls.map(get_graphql_url);
+
+        // THEN
+        assert_eq!(
+            result,
+            [
+                "https://api.github.com/graphql",
+                "https://github.example.com/api/graphql",
+            ]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use super::super::client::HttpClient;
+use super::super::forge::MAX_COMMITS_PER_LOG;
+use super::super::pagination::get_link_from_headers;
+use super::request::get;
+use crate::domain::{Commit, CommitLog, Repo, encode_ref};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+const COMMITS_PER_PAGE: usize = 100;
+
+#[derive(Debug, Deserialize)]
+struct CommitSha {
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompareResponse {
+    commits: Vec<Commit>,
+    html_url: String,
+    total_commits: usize,
+    ahead_by: usize,
+    behind_by: usize,
+}
+
+pub(crate) async fn get_commit_log(
+    client: &HttpClient,
+    repo: &Repo,
+    base_ref: &str,
+    head_ref: &str,
+    token: Option<&str>,
+) -> anyhow::Result<CommitLog> {
+    let url = format!(
+        "{}/compare/{}...{}?per_page={}",
+        repo.api_url(),
+        encode_ref(base_ref),
+        encode_ref(head_ref),
+        COMMITS_PER_PAGE
+    );
+
+    let (first_page, headers) = get::<CompareResponse>(client, &url, token).await?;
+
+    // GitHub returns commits in chronological order, one page at a time; when there are more
+    // commits than we're willing to show, walk backwards from the last page so that the most
+    // recent ones are kept
+    let mut commits = match get_link_from_headers(&headers, "last") {
+        Some(last_url) if first_page.total_commits > MAX_COMMITS_PER_LOG => {
+            let mut newest_first = Vec::new();
+            let mut next_url = Some(last_url);
+            while let Some(url) = next_url
+                && newest_first.len() < MAX_COMMITS_PER_LOG
+            {
+                let (page, headers) = get::<CompareResponse>(client, &url, token).await?;
+                newest_first.extend(page.commits.into_iter().rev());
+                next_url = get_link_from_headers(&headers, "prev");
+            }
+            newest_first
+        }
+        _ => {
+            let mut commits = first_page.commits;
+            let mut next_url = get_link_from_headers(&headers, "next");
+            while let Some(url) = next_url {
+                let (page, headers) = get::<CompareResponse>(client, &url, token).await?;
+                commits.extend(page.commits);
+                next_url = get_link_from_headers(&headers, "next");
+            }
+            commits.reverse();
+            commits
+        }
+    };
+
+    commits.truncate(MAX_COMMITS_PER_LOG);
+
+    Ok(CommitLog {
+        repo: repo.clone(),
+        package: None,
+        base_ref: base_ref.to_string(),
+        head_ref: head_ref.to_string(),
+        commits,
+        html_url: first_page.html_url,
+        total_commits: first_page.total_commits,
+        ahead_by: first_page.ahead_by,
+        behind_by: first_page.behind_by,
+        total_is_lower_bound: false,
+    })
+}
+
+pub(crate) async fn get_recent_commits(
+    client: &HttpClient,
+    repo: &Repo,
+    path: Option<&str>,
+    max_commits: usize,
+    since: Option<DateTime<Utc>>,
+    token: Option<&str>,
+) -> anyhow::Result<Vec<Commit>> {
+    let mut url = Url::parse(&format!("{}/commits", repo.api_url()))
+        .context("couldn't build URL for GitHub API")?;
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("sha", repo.head_ref.name())
+            .append_pair("per_page", &max_commits.min(COMMITS_PER_PAGE).to_string());
+        if let Some(path) = path {
+            query.append_pair("path", path);
+        }
+        if let Some(since) = since {
+            query.append_pair("since", &since.to_rfc3339());
+        }
+    }
+
+    let mut commits = Vec::new();
+    let mut next_url = Some(url.to_string());
+    while let Some(url) = next_url
+        && commits.len() < max_commits
+    {
+        let (page, headers): (Vec<Commit>, _) = get(client, &url, token).await?;
+        commits.extend(page);
+        next_url = get_link_from_headers(&headers, "next");
+    }
+
+    commits.truncate(max_commits);
+
+    Ok(commits)
+}
+
+pub(crate) async fn get_shas_touching_paths(
+    client: &HttpClient,
+    repo: &Repo,
+    paths: &[String],
+    since: DateTime<Utc>,
+    token: Option<&str>,
+) -> anyhow::Result<HashSet<String>> {
+    let mut shas = HashSet::new();
+
+    for path in paths {
+        let mut url = Url::parse(&format!("{}/commits", repo.api_url()))
+            .context("couldn't build URL for GitHub API")?;
+        url.query_pairs_mut()
+            .append_pair("sha", repo.head_ref.name())
+            .append_pair("path", path)
+            .append_pair("since", &since.to_rfc3339())
+            .append_pair("per_page", &COMMITS_PER_PAGE.to_string());
+
+        let mut next_url = Some(url.to_string());
+        while let Some(url) = next_url {
+            let (page, headers): (Vec<CommitSha>, _) = get(client, &url, token).await?;
+            shas.extend(page.into_iter().map(|c| c.sha));
+            next_url = get_link_from_headers(&headers, "next");
+        }
+    }
+
+    Ok(shas)
+}
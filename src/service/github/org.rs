@@ -0,0 +1,27 @@
+use super::super::client::HttpClient;
+use super::super::pagination::get_link_from_headers;
+use super::request::get;
+use crate::domain::{DiscoveredRepo, Org};
+
+const REPOS_PER_PAGE: usize = 100;
+
+pub(crate) async fn get_org_repos(
+    client: &HttpClient,
+    org: &Org,
+    token: Option<&str>,
+) -> anyhow::Result<Vec<DiscoveredRepo>> {
+    let mut repos = Vec::new();
+    let mut next_url = Some(format!(
+        "{}?type=all&per_page={}",
+        org.repos_api_url(),
+        REPOS_PER_PAGE
+    ));
+
+    while let Some(url) = next_url {
+        let (page, headers): (Vec<DiscoveredRepo>, _) = get(client, &url, token).await?;
+        repos.extend(page);
+        next_url = get_link_from_headers(&headers, "next");
+    }
+
+    Ok(repos)
+}
@@ -0,0 +1,63 @@
+use super::super::client::HttpClient;
+use super::super::pagination::get_link_from_headers;
+use super::request::{get, parse_response, send_request};
+use crate::domain::{LastRelease, Repo};
+use reqwest::StatusCode;
+
+const RELEASES_PER_PAGE: usize = 100;
+
+pub(crate) async fn get_latest_release(
+    client: &HttpClient,
+    repo: &Repo,
+    consider_prereleases: bool,
+    token: Option<&str>,
+) -> anyhow::Result<Option<LastRelease>> {
+    if consider_prereleases {
+        get_latest_release_from_list(client, repo, token).await
+    } else {
+        get_latest_full_release(client, repo, token).await
+    }
+}
+
+// GitHub's "latest release" is the most recent non-prerelease, non-draft release; it responds
+// with a 404 if there isn't one
+async fn get_latest_full_release(
+    client: &HttpClient,
+    repo: &Repo,
+    token: Option<&str>,
+) -> anyhow::Result<Option<LastRelease>> {
+    let url = format!("{}/releases/latest", repo.api_url());
+
+    let response = send_request(client, &url, token).await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    let (release, _): (LastRelease, _) = parse_response(response).await?;
+
+    Ok(Some(release))
+}
+
+async fn get_latest_release_from_list(
+    client: &HttpClient,
+    repo: &Repo,
+    token: Option<&str>,
+) -> anyhow::Result<Option<LastRelease>> {
+    let mut next_url = Some(format!(
+        "{}/releases?per_page={}",
+        repo.api_url(),
+        RELEASES_PER_PAGE
+    ));
+
+    while let Some(url) = next_url {
+        let (releases, headers): (Vec<LastRelease>, _) = get(client, &url, token).await?;
+
+        if let Some(release) = releases.into_iter().find(|r| !r.draft) {
+            return Ok(Some(release));
+        }
+
+        next_url = get_link_from_headers(&headers, "next");
+    }
+
+    Ok(None)
+}
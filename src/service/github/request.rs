@@ -0,0 +1,81 @@
+use super::super::client::HttpClient;
+use anyhow::Context;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+pub(super) async fn send_request(
+    client: &HttpClient,
+    url: &str,
+    token: Option<&str>,
+) -> anyhow::Result<reqwest::Response> {
+    let mut request = client
+        .get(url)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .header("User-Agent", "unreleased@v0.1.0");
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    client
+        .send(request)
+        .await
+        .context("failed to send request to GitHub API")
+}
+
+pub(super) async fn parse_response<T>(response: reqwest::Response) -> anyhow::Result<(T, HeaderMap)>
+where
+    T: DeserializeOwned,
+{
+    let status = response.status();
+    if !status.is_success() {
+        match response.text().await {
+            Ok(body) => anyhow::bail!("GitHub API request failed with status {}: {}", status, body),
+            Err(_) => anyhow::bail!("GitHub API request failed with status {}", status,),
+        }
+    }
+
+    let headers = response.headers().clone();
+    let body: T = response
+        .json()
+        .await
+        .context("failed to parse GitHub API response")?;
+
+    Ok((body, headers))
+}
+
+pub(super) async fn get<T>(
+    client: &HttpClient,
+    url: &str,
+    token: Option<&str>,
+) -> anyhow::Result<(T, HeaderMap)>
+where
+    T: DeserializeOwned,
+{
+    let response = send_request(client, url, token).await?;
+    parse_response(response).await
+}
+
+pub(super) async fn post_graphql<T>(
+    client: &HttpClient,
+    url: &str,
+    body: &serde_json::Value,
+    token: &str,
+) -> anyhow::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let request = client
+        .post(url)
+        .header("User-Agent", "unreleased@v0.1.0")
+        .header("Authorization", format!("Bearer {}", token))
+        .json(body);
+
+    let response = client
+        .send(request)
+        .await
+        .context("failed to send request to GitHub's GraphQL API")?;
+    let (body, _) = parse_response(response).await?;
+
+    Ok(body)
+}
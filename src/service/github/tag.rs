@@ -0,0 +1,29 @@
+use super::super::client::HttpClient;
+use super::super::pagination::get_link_from_headers;
+use super::request::get;
+use crate::domain::{Repo, Tag};
+
+const TAGS_PER_PAGE: usize = 100;
+
+pub(crate) async fn get_tags(
+    client: &HttpClient,
+    repo: &Repo,
+    token: Option<&str>,
+) -> anyhow::Result<Vec<Tag>> {
+    let mut tags = Vec::new();
+    let mut next_url = Some(format!(
+        "{}/tags?per_page={}",
+        repo.api_url(),
+        TAGS_PER_PAGE
+    ));
+
+    // tags are listed in reverse lexicographic order, which isn't the same as semver order, so
+    // all of them need to be looked at
+    while let Some(url) = next_url {
+        let (page, headers): (Vec<Tag>, _) = get(client, &url, token).await?;
+        tags.extend(page);
+        next_url = get_link_from_headers(&headers, "next");
+    }
+
+    Ok(tags)
+}
@@ -0,0 +1,204 @@
+use super::super::client::HttpClient;
+use super::super::forge::MAX_COMMITS_PER_LOG;
+use super::super::pagination::get_link_from_headers;
+use super::request::get;
+use crate::domain::{Author, Commit, CommitDetail, CommitLog, Repo};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+const COMMITS_PER_PAGE: usize = 100;
+
+#[derive(Debug, Deserialize)]
+struct GitLabCommit {
+    id: String,
+    message: String,
+    author_name: String,
+    authored_date: DateTime<Utc>,
+    web_url: String,
+}
+
+impl From<GitLabCommit> for Commit {
+    fn from(value: GitLabCommit) -> Self {
+        Commit {
+            sha: value.id,
+            commit: CommitDetail {
+                message: value.message,
+                author: Author {
+                    name: value.author_name,
+                    date: value.authored_date,
+                },
+            },
+            html_url: value.web_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitId {
+    id: String,
+}
+
+pub(crate) async fn get_commit_log(
+    client: &HttpClient,
+    repo: &Repo,
+    base_ref: &str,
+    head_ref: &str,
+    token: Option<&str>,
+) -> anyhow::Result<CommitLog> {
+    // the compare endpoint returns every commit in one go, without counting them; the commits
+    // endpoint pages through a range instead, and reports its size via X-Total
+    let url = get_range_url(repo, base_ref, head_ref)?;
+
+    let mut commits = Vec::new();
+    let mut reported_total = None;
+    let mut next_url = Some(url.to_string());
+    while commits.len() < MAX_COMMITS_PER_LOG
+        && let Some(url) = next_url.take()
+    {
+        let (page, headers): (Vec<GitLabCommit>, _) = get(client, &url, token).await?;
+        reported_total = reported_total.or(get_total_from_headers(&headers));
+        commits.extend(page.into_iter().map(Commit::from));
+        next_url = get_link_from_headers(&headers, "next");
+    }
+
+    let total_commits = match reported_total {
+        Some(total) => total,
+        None => commits.len() + count_commits(client, next_url, token).await?,
+    };
+    commits.truncate(MAX_COMMITS_PER_LOG);
+
+    // GitLab doesn't report how far behind the base ref the head ref is, so that's counted via the
+    // reverse range
+    let url = get_range_url(repo, head_ref, base_ref)?;
+    let (page, headers): (Vec<CommitId>, _) = get(client, url.as_str(), token).await?;
+    let behind_by = match get_total_from_headers(&headers) {
+        Some(total) => total,
+        None => {
+            page.len()
+                + count_commits(client, get_link_from_headers(&headers, "next"), token).await?
+        }
+    };
+
+    Ok(CommitLog {
+        repo: repo.clone(),
+        package: None,
+        base_ref: base_ref.to_string(),
+        head_ref: head_ref.to_string(),
+        commits,
+        html_url: repo.compare_web_url(base_ref, head_ref),
+        total_commits,
+        ahead_by: total_commits,
+        behind_by,
+        total_is_lower_bound: false,
+    })
+}
+
+pub(crate) async fn get_recent_commits(
+    client: &HttpClient,
+    repo: &Repo,
+    path: Option<&str>,
+    max_commits: usize,
+    since: Option<DateTime<Utc>>,
+    token: Option<&str>,
+) -> anyhow::Result<Vec<Commit>> {
+    let url = get_commits_url(repo, path, since, max_commits.min(COMMITS_PER_PAGE))?;
+
+    let mut commits = Vec::new();
+    let mut next_url = Some(url.to_string());
+    while let Some(url) = next_url
+        && commits.len() < max_commits
+    {
+        let (page, headers): (Vec<GitLabCommit>, _) = get(client, &url, token).await?;
+        commits.extend(page.into_iter().map(Commit::from));
+        next_url = get_link_from_headers(&headers, "next");
+    }
+
+    commits.truncate(max_commits);
+
+    Ok(commits)
+}
+
+pub(crate) async fn get_shas_touching_paths(
+    client: &HttpClient,
+    repo: &Repo,
+    paths: &[String],
+    since: DateTime<Utc>,
+    token: Option<&str>,
+) -> anyhow::Result<HashSet<String>> {
+    let mut shas = HashSet::new();
+
+    for path in paths {
+        let url = get_commits_url(repo, Some(path), Some(since), COMMITS_PER_PAGE)?;
+
+        let mut next_url = Some(url.to_string());
+        while let Some(url) = next_url {
+            let (page, headers): (Vec<CommitId>, _) = get(client, &url, token).await?;
+            shas.extend(page.into_iter().map(|c| c.id));
+            next_url = get_link_from_headers(&headers, "next");
+        }
+    }
+
+    Ok(shas)
+}
+
+fn get_commits_url(
+    repo: &Repo,
+    path: Option<&str>,
+    since: Option<DateTime<Utc>>,
+    per_page: usize,
+) -> anyhow::Result<Url> {
+    let mut url = Url::parse(&format!("{}/repository/commits", repo.api_url()))
+        .context("couldn't build URL for GitLab API")?;
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("ref_name", repo.head_ref.name())
+            .append_pair("per_page", &per_page.to_string());
+        if let Some(path) = path {
+            query.append_pair("path", path);
+        }
+        if let Some(since) = since {
+            query.append_pair("since", &since.to_rfc3339());
+        }
+    }
+
+    Ok(url)
+}
+
+// the commits reachable from the head ref, but not from the base ref, newest first
+fn get_range_url(repo: &Repo, base_ref: &str, head_ref: &str) -> anyhow::Result<Url> {
+    let mut url = Url::parse(&format!("{}/repository/commits", repo.api_url()))
+        .context("couldn't build URL for GitLab API")?;
+    url.query_pairs_mut()
+        .append_pair("ref_name", &format!("{}..{}", base_ref, head_ref))
+        .append_pair("per_page", &COMMITS_PER_PAGE.to_string());
+
+    Ok(url)
+}
+
+// GitLab leaves X-Total out for large ranges, whose commits are then counted page by page
+async fn count_commits(
+    client: &HttpClient,
+    mut next_url: Option<String>,
+    token: Option<&str>,
+) -> anyhow::Result<usize> {
+    let mut count = 0;
+    while let Some(url) = next_url {
+        let (page, headers): (Vec<CommitId>, _) = get(client, &url, token).await?;
+        count += page.len();
+        next_url = get_link_from_headers(&headers, "next");
+    }
+
+    Ok(count)
+}
+
+fn get_total_from_headers(headers: &HeaderMap) -> Option<usize> {
+    headers
+        .get("x-total")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
@@ -0,0 +1,49 @@
+use super::super::client::HttpClient;
+use super::super::pagination::get_link_from_headers;
+use super::super::tag::is_prerelease;
+use super::request::get;
+use crate::domain::{LastRelease, Repo};
+use serde::Deserialize;
+
+const RELEASES_PER_PAGE: usize = 100;
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    upcoming_release: bool,
+}
+
+// GitLab has no notion of drafts or prereleases; releases with a release date in the future are
+// skipped instead, and releases whose tags have a semver prerelease component are considered
+// prereleases
+pub(crate) async fn get_latest_release(
+    client: &HttpClient,
+    repo: &Repo,
+    consider_prereleases: bool,
+    token: Option<&str>,
+) -> anyhow::Result<Option<LastRelease>> {
+    let mut next_url = Some(format!(
+        "{}/releases?order_by=released_at&sort=desc&per_page={}",
+        repo.api_url(),
+        RELEASES_PER_PAGE
+    ));
+
+    while let Some(url) = next_url {
+        let (releases, headers): (Vec<Release>, _) = get(client, &url, token).await?;
+
+        if let Some(release) = releases
+            .into_iter()
+            .find(|r| !r.upcoming_release && (consider_prereleases || !is_prerelease(&r.tag_name)))
+        {
+            return Ok(Some(LastRelease {
+                tag_name: release.tag_name,
+                draft: false,
+            }));
+        }
+
+        next_url = get_link_from_headers(&headers, "next");
+    }
+
+    Ok(None)
+}
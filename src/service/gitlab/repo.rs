@@ -0,0 +1,16 @@
+use super::super::client::HttpClient;
+use super::request::get;
+use crate::domain::{Repo, RepoDetails};
+use anyhow::Context;
+
+pub(crate) async fn get_default_branch(
+    client: &HttpClient,
+    repo: &Repo,
+    token: Option<&str>,
+) -> anyhow::Result<String> {
+    let (details, _): (RepoDetails, _) = get(client, &repo.api_url(), token).await?;
+
+    details
+        .default_branch
+        .context("repo doesn't have a default branch")
+}
@@ -0,0 +1,225 @@
+use super::forge::MAX_COMMITS_PER_LOG;
+use crate::domain::{Author, Commit, CommitDetail, CommitLog, Repo, Tag};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use tokio::process::Command;
+
+// fields are separated by the ASCII unit separator, and commits by the record separator, since
+// neither is expected to show up in commit messages
+const LOG_FORMAT: &str = "--format=%H%x1f%an%x1f%aI%x1f%B%x1e";
+const FIELD_SEPARATOR: char = '\x1f';
+const RECORD_SEPARATOR: char = '\x1e';
+// refs come from the config, so they're always passed after this, to keep one that starts with a
+// "-" from being taken as an option
+const END_OF_OPTIONS: &str = "--end-of-options";
+
+// the branch that's checked out is used as the default branch of local repos
+pub(super) async fn get_default_branch(repo: &Repo) -> anyhow::Result<String> {
+    let output = run_git(repo, &["rev-parse", "--abbrev-ref", "HEAD"]).await?;
+
+    Ok(output.trim().to_string())
+}
+
+pub(super) async fn get_tags(repo: &Repo) -> anyhow::Result<Vec<Tag>> {
+    let output = run_git(repo, &["tag", "--list"]).await?;
+
+    Ok(output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|name| Tag {
+            name: name.to_string(),
+        })
+        .collect())
+}
+
+pub(super) async fn get_commit_log(
+    repo: &Repo,
+    base_ref: &str,
+    head_ref: &str,
+) -> anyhow::Result<CommitLog> {
+    let range = format!("{}...{}", base_ref, head_ref);
+    let counts = run_git(
+        repo,
+        &[
+            "rev-list",
+            "--left-right",
+            "--count",
+            END_OF_OPTIONS,
+            &range,
+            "--",
+        ],
+    )
+    .await?;
+    let (behind_by, ahead_by) = counts
+        .trim()
+        .split_once('\t')
+        .and_then(|(behind, ahead)| Some((behind.parse().ok()?, ahead.parse().ok()?)))
+        .with_context(|| format!("couldn't parse commit counts from git: {:?}", counts))?;
+
+    let max_count = format!("--max-count={}", MAX_COMMITS_PER_LOG);
+    let range = format!("{}..{}", base_ref, head_ref);
+    let output = run_git(
+        repo,
+        &["log", LOG_FORMAT, &max_count, END_OF_OPTIONS, &range, "--"],
+    )
+    .await?;
+    let commits = parse_log(&output)?;
+
+    Ok(CommitLog {
+        repo: repo.clone(),
+        package: None,
+        base_ref: base_ref.to_string(),
+        head_ref: head_ref.to_string(),
+        commits,
+        html_url: repo.compare_web_url(base_ref, head_ref),
+        total_commits: ahead_by,
+        ahead_by,
+        behind_by,
+        total_is_lower_bound: false,
+    })
+}
+
+pub(super) async fn get_recent_commits(
+    repo: &Repo,
+    path: Option<&str>,
+    max_commits: usize,
+    since: Option<DateTime<Utc>>,
+) -> anyhow::Result<Vec<Commit>> {
+    let max_count = format!("--max-count={}", max_commits);
+    let since = since.map(|s| format!("--since={}", s.to_rfc3339()));
+
+    let mut args = vec!["log", LOG_FORMAT, &max_count];
+    if let Some(since) = &since {
+        args.push(since);
+    }
+    args.extend([END_OF_OPTIONS, repo.head_ref.name(), "--"]);
+    if let Some(path) = path {
+        args.push(path);
+    }
+
+    let output = run_git(repo, &args).await?;
+
+    parse_log(&output)
+}
+
+pub(super) async fn get_shas_touching_paths(
+    repo: &Repo,
+    paths: &[String],
+    since: DateTime<Utc>,
+) -> anyhow::Result<HashSet<String>> {
+    let since = format!("--since={}", since.to_rfc3339());
+
+    let mut args = vec![
+        "log",
+        "--format=%H",
+        &since,
+        END_OF_OPTIONS,
+        repo.head_ref.name(),
+        "--",
+    ];
+    args.extend(paths.iter().map(String::as_str));
+
+    let output = run_git(repo, &args).await?;
+
+    Ok(output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|sha| sha.to_string())
+        .collect())
+}
+
+async fn run_git(repo: &Repo, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&repo.repo)
+        .args(args)
+        .output()
+        .await
+        .context("couldn't run \"git\"")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "\"git {}\" failed; stderr: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn parse_log(output: &str) -> anyhow::Result<Vec<Commit>> {
+    output
+        .split(RECORD_SEPARATOR)
+        .map(str::trim_start)
+        .filter(|record| !record.is_empty())
+        .map(parse_commit)
+        .collect()
+}
+
+fn parse_commit(record: &str) -> anyhow::Result<Commit> {
+    let mut fields = record.splitn(4, FIELD_SEPARATOR);
+    let (Some(sha), Some(author), Some(date), Some(message)) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        anyhow::bail!("couldn't parse commit from git log output: {:?}", record);
+    };
+
+    let date = DateTime::parse_from_rfc3339(date)
+        .with_context(|| format!("couldn't parse date of commit {}: {:?}", sha, date))?;
+
+    Ok(Commit {
+        sha: sha.to_string(),
+        commit: CommitDetail {
+            message: message.trim_end().to_string(),
+            author: Author {
+                name: author.to_string(),
+                date: date.with_timezone(&Utc),
+            },
+        },
+        html_url: String::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_git_log_output_works() {
+        // GIVEN
+        let output = "ae7de14\x1fUser A\x1f2025-01-15T10:00:00+05:30\x1fadd tracing support\n\nsome more details\n\x1e\n2ff3e97\x1fUser B\x1f2025-01-14T10:00:00Z\x1fallow configuring table style\n\x1e\n";
+
+        // WHEN
+        let result = parse_log(output).expect("result should've been Ok");
+
+        // THEN
+        insta::assert_debug_snapshot!(result, @r#"
+        [
+            Commit {
+                sha: "ae7de14",
+                commit: CommitDetail {
+                    message: "add tracing support\n\nsome more details",
+                    author: Author {
+                        name: "User A",
+                        date: 2025-01-15T04:30:00Z,
+                    },
+                },
+                html_url: "",
+            },
+            Commit {
+                sha: "2ff3e97",
+                commit: CommitDetail {
+                    message: "allow configuring table style",
+                    author: Author {
+                        name: "User B",
+                        date: 2025-01-14T10:00:00Z,
+                    },
+                },
+                html_url: "",
+            },
+        ]
+        "#);
+    }
+}
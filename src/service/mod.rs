@@ -1,6 +1,22 @@
+mod batch;
+mod cache;
+mod client;
+mod discover;
 mod execute;
-mod log;
-mod release;
+mod forge;
+mod gitea;
+mod github;
+mod gitlab;
+mod local;
+mod never_released;
+mod package;
+mod pagination;
+mod progress;
 mod result;
+mod tag;
 
+pub use cache::{ResponseCache, clear_cache, get_default_cache_dir};
+pub use client::HttpClient;
+pub use discover::*;
 pub use execute::*;
+pub use progress::Progress;
@@ -0,0 +1,56 @@
+use super::client::HttpClient;
+use super::forge::get_recent_commits;
+use crate::domain::{FetchConfig, NeverReleasedLog, Package, Repo};
+use anyhow::Context;
+use chrono::{Duration, Utc};
+use std::collections::HashSet;
+
+pub(super) async fn get_never_released_log(
+    client: &HttpClient,
+    repo: &Repo,
+    package: Option<&Package>,
+    fetch_config: &FetchConfig,
+    token: Option<&str>,
+) -> anyhow::Result<NeverReleasedLog> {
+    let max_commits = fetch_config.never_released_max_commits;
+    let since = fetch_config
+        .never_released_max_age_days
+        .map(|days| Utc::now() - Duration::days(i64::from(days)));
+
+    let paths = package.map(|p| p.paths.as_slice()).unwrap_or_default();
+
+    let commits = if paths.is_empty() {
+        get_recent_commits(client, repo, None, max_commits, since, token)
+            .await
+            .context("couldn't fetch recent commits")?
+    } else {
+        let mut commits = Vec::new();
+        let mut seen = HashSet::new();
+        for path in paths {
+            let path_commits =
+                get_recent_commits(client, repo, Some(path), max_commits, since, token)
+                    .await
+                    .with_context(|| {
+                        format!("couldn't fetch recent commits for path \"{}\"", path)
+                    })?;
+
+            commits.extend(
+                path_commits
+                    .into_iter()
+                    .filter(|c| seen.insert(c.sha.clone())),
+            );
+        }
+
+        commits.sort_by_key(|c| std::cmp::Reverse(c.commit.author.date));
+        commits.truncate(max_commits);
+        commits
+    };
+
+    Ok(NeverReleasedLog {
+        repo: repo.clone(),
+        package: package.map(|p| p.name.clone()),
+        head_ref: repo.head_ref.name().to_string(),
+        commits,
+        html_url: repo.commits_web_url(repo.head_ref.name()),
+    })
+}
@@ -0,0 +1,65 @@
+use super::client::HttpClient;
+use super::forge::{get_commit_log, get_shas_touching_paths};
+use super::never_released::get_never_released_log;
+use super::tag::get_highest_semver_tag;
+use crate::domain::{FetchConfig, Package, Repo, RepoChangelog, Tag};
+use anyhow::Context;
+use std::collections::HashSet;
+
+pub(super) async fn get_changelog_for_package(
+    client: &HttpClient,
+    repo: &Repo,
+    package: &Package,
+    tags: &[Tag],
+    fetch_config: &FetchConfig,
+    token: Option<&str>,
+) -> anyhow::Result<RepoChangelog> {
+    let latest_tag = get_highest_semver_tag(
+        tags,
+        Some(&package.tag_prefix),
+        repo.tag_pattern.as_ref(),
+        repo.consider_prereleases,
+    );
+
+    let latest_tag = match latest_tag {
+        Some(t) => t,
+        None => {
+            let log =
+                get_never_released_log(client, repo, Some(package), fetch_config, token).await?;
+            return Ok(RepoChangelog::NeverReleased(log));
+        }
+    };
+
+    let mut commit_log =
+        get_commit_log(client, repo, &latest_tag.name, repo.head_ref.name(), token)
+            .await
+            .context("couldn't fetch commits")?;
+    commit_log.package = Some(package.name.clone());
+
+    if package.paths.is_empty() {
+        return Ok(RepoChangelog::Released(commit_log));
+    }
+
+    let oldest_commit_date = commit_log
+        .commits
+        .iter()
+        .map(|c| c.commit.author.date)
+        .min();
+
+    let relevant_shas = match oldest_commit_date {
+        Some(since) => get_shas_touching_paths(client, repo, &package.paths, since, token)
+            .await
+            .context("couldn't fetch commits for the package's paths")?,
+        None => HashSet::new(),
+    };
+
+    // only the commits fetched from the compare endpoint can be attributed to the package; if
+    // that's not all of them, the package may have more commits than the ones counted
+    commit_log.total_is_lower_bound = commit_log.is_truncated();
+    commit_log
+        .commits
+        .retain(|commit| relevant_shas.contains(&commit.sha));
+    commit_log.total_commits = commit_log.commits.len();
+
+    Ok(RepoChangelog::Released(commit_log))
+}
@@ -0,0 +1,76 @@
+use reqwest::header::{HeaderMap, LINK};
+
+pub(super) fn get_link_from_headers(headers: &HeaderMap, rel: &str) -> Option<String> {
+    headers
+        .get(LINK)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| get_link(value, rel))
+}
+
+fn get_link(link_header: &str, rel: &str) -> Option<String> {
+    link_header.split(',').find_map(|part| {
+        let (url, params) = part.trim().split_once(';')?;
+        let url = url.trim().strip_prefix('<')?.strip_suffix('>')?;
+
+        params
+            .split(';')
+            .filter_map(|param| param.trim().split_once('='))
+            .any(|(key, value)| key.trim() == "rel" && value.trim().trim_matches('"') == rel)
+            .then(|| url.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINK_HEADER: &str = r#"<https://api.github.com/repositories/1/compare/v1...main?per_page=100&page=2>; rel="next", <https://api.github.com/repositories/1/compare/v1...main?per_page=100&page=5>; rel="last""#;
+
+    #[test]
+    fn getting_next_link_works() {
+        // GIVEN
+        // WHEN
+        let result = get_link(LINK_HEADER, "next");
+
+        // THEN
+        assert_eq!(
+            result.as_deref(),
+            Some("https://api.github.com/repositories/1/compare/v1...main?per_page=100&page=2")
+        );
+    }
+
+    #[test]
+    fn getting_last_link_works() {
+        // GIVEN
+        // WHEN
+        let result = get_link(LINK_HEADER, "last");
+
+        // THEN
+        assert_eq!(
+            result.as_deref(),
+            Some("https://api.github.com/repositories/1/compare/v1...main?per_page=100&page=5")
+        );
+    }
+
+    #[test]
+    fn getting_absent_link_returns_none() {
+        // GIVEN
+        // WHEN
+        let result = get_link(LINK_HEADER, "prev");
+
+        // THEN
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn getting_link_from_malformed_header_returns_none() {
+        // GIVEN
+        let header = r#"https://api.github.com/repositories/1/releases?page=2; rel="next""#;
+
+        // WHEN
+        let result = get_link(header, "next");
+
+        // THEN
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::domain::Repo;
+use std::io::{IsTerminal, Write};
+use std::sync::{Arc, Mutex};
+
+// the status line only names a few of the repos in flight, so that it fits on one line
+const MAX_REPOS_SHOWN: usize = 3;
+
+/// Reports on stderr how far along fetching changelogs is: a status line with the number of repos
+/// done, and the ones in flight, and a line for every repo that fails, as it happens. On a
+/// terminal, the status line is redrawn in place; elsewhere (eg. when forced on in CI), every
+/// update is printed on a line of its own.
+#[derive(Debug, Clone)]
+pub struct Progress {
+    state: Option<Arc<Mutex<ProgressState>>>,
+}
+
+#[derive(Debug)]
+struct ProgressState {
+    redraw: bool,
+    total: usize,
+    done: usize,
+    in_flight: Vec<String>,
+}
+
+impl Progress {
+    pub fn new(enabled: bool) -> Self {
+        let state = enabled.then(|| {
+            Arc::new(Mutex::new(ProgressState {
+                redraw: std::io::stderr().is_terminal(),
+                total: 0,
+                done: 0,
+                in_flight: Vec::new(),
+            }))
+        });
+
+        Self { state }
+    }
+
+    pub(super) fn set_total(&self, total: usize) {
+        self.update(|state| {
+            state.total = total;
+            None
+        });
+    }
+
+    pub(super) fn start(&self, repos: &[Repo]) {
+        self.update(|state| {
+            state.in_flight.extend(repos.iter().map(get_name));
+            None
+        });
+    }
+
+    pub(super) fn finish(&self, repos: &[Repo]) {
+        self.update(|state| {
+            for name in repos.iter().map(get_name) {
+                if let Some(index) = state.in_flight.iter().position(|n| n == &name) {
+                    state.in_flight.remove(index);
+                }
+            }
+            state.done += repos.len();
+            None
+        });
+    }
+
+    pub(super) fn report_failure(&self, repo: &Repo, error: &anyhow::Error) {
+        self.update(|_| Some(format!("failed: {}: {:#}", get_name(repo), error)));
+    }
+
+    /// Removes the status line, so that it doesn't get mixed up with whatever is printed next
+    pub(super) fn clear(&self) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let Ok(state) = state.lock() else {
+            return;
+        };
+
+        if state.redraw {
+            let _ = write!(std::io::stderr().lock(), "\r\x1b[2K");
+        }
+    }
+
+    // on a terminal, the message an update returns (if any) is printed above the redrawn status
+    // line; otherwise, it's printed instead of the status line, which is printed again by the
+    // next update anyway
+    fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut ProgressState) -> Option<String>,
+    {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let Ok(mut state) = state.lock() else {
+            return;
+        };
+
+        let message = f(&mut state);
+        let status = render_status(state.done, state.total, &state.in_flight);
+
+        let mut stderr = std::io::stderr().lock();
+        let _ = if state.redraw {
+            match message {
+                Some(message) => write!(stderr, "\r\x1b[2K{}\n{}", message, status),
+                None => write!(stderr, "\r\x1b[2K{}", status),
+            }
+        } else {
+            match message {
+                Some(message) => writeln!(stderr, "{}", message),
+                None => writeln!(stderr, "{}", status),
+            }
+        };
+        let _ = stderr.flush();
+    }
+}
+
+fn get_name(repo: &Repo) -> String {
+    match &repo.label {
+        Some(label) => format!("{} ({})", repo, label),
+        None => repo.to_string(),
+    }
+}
+
+fn render_status(done: usize, total: usize, in_flight: &[String]) -> String {
+    let mut status = format!("fetched {}/{} repos", done, total);
+    if in_flight.is_empty() {
+        return status;
+    }
+
+    status.push_str("; in flight: ");
+    status.push_str(
+        &in_flight
+            .iter()
+            .take(MAX_REPOS_SHOWN)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    if in_flight.len() > MAX_REPOS_SHOWN {
+        status.push_str(&format!(" (+{} more)", in_flight.len() - MAX_REPOS_SHOWN));
+    }
+
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    #[test]
+    fn status_names_a_few_of_the_repos_in_flight() {
+        // GIVEN
+        let in_flight = (0..5)
+            .map(|i| format!("owner/repo-{}", i))
+            .collect::<Vec<_>>();
+
+        // WHEN
+        let result = render_status(3, 10, &in_flight);
+
+        // THEN
+        assert_snapshot!(result, @"fetched 3/10 repos; in flight: owner/repo-0, owner/repo-1, owner/repo-2 (+2 more)");
+    }
+
+    #[test]
+    fn status_only_shows_counts_when_nothing_is_in_flight() {
+        // GIVEN
+        // WHEN
+        let result = render_status(10, 10, &[]);
+
+        // THEN
+        assert_snapshot!(result, @"fetched 10/10 repos");
+    }
+}
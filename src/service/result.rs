@@ -1,24 +1,98 @@
-use super::log::get_commit_log;
-use super::release::get_latest_release;
-use crate::domain::{CommitLog, Repo};
+use super::client::HttpClient;
+use super::forge::{get_commit_log, get_default_branch, get_latest_release, get_tags};
+use super::never_released::get_never_released_log;
+use super::package::get_changelog_for_package;
+use super::tag::get_highest_semver_tag;
+use crate::domain::{Baseline, FetchConfig, HeadRef, Repo, RepoChangelog};
 use anyhow::Context;
 
-pub(super) async fn get_changelog_for_repo(
+pub(super) async fn get_changelogs_for_repo(
+    client: &HttpClient,
     repo: &Repo,
-    token: &str,
-) -> anyhow::Result<Option<CommitLog>> {
-    let latest_release = get_latest_release(repo, repo.consider_prereleases, token)
-        .await
-        .context("couldn't get the latest release")?;
+    fetch_config: &FetchConfig,
+    token: Option<&str>,
+) -> anyhow::Result<Vec<RepoChangelog>> {
+    let repo = &with_resolved_head_ref(client, repo, token).await?;
+
+    if !repo.packages.is_empty() {
+        let tags = get_tags(client, repo, token)
+            .await
+            .context("couldn't get the repo's tags")?;
+
+        let mut changelogs = Vec::new();
+        for package in &repo.packages {
+            let changelog =
+                get_changelog_for_package(client, repo, package, &tags, fetch_config, token)
+                    .await
+                    .with_context(|| {
+                        format!("couldn't get changes for package \"{}\"", package.name)
+                    })?;
+
+            changelogs.push(changelog);
+        }
+
+        return Ok(changelogs);
+    }
+
+    let base_ref = match repo.baseline {
+        Baseline::Release => get_latest_release(client, repo, token)
+            .await
+            .context("couldn't get the latest release")?
+            .map(|release| release.tag_name),
+        Baseline::Tag => {
+            let tags = get_tags(client, repo, token)
+                .await
+                .context("couldn't get the repo's tags")?;
+
+            get_highest_semver_tag(
+                &tags,
+                None,
+                repo.tag_pattern.as_ref(),
+                repo.consider_prereleases,
+            )
+            .map(|tag| tag.name.clone())
+        }
+    };
 
-    let latest_release = match latest_release {
-        Some(r) => r,
-        None => return Ok(None),
+    let changelog =
+        get_changelog_from_base_ref(client, repo, base_ref.as_deref(), fetch_config, token).await?;
+
+    Ok(vec![changelog])
+}
+
+/// Repos without a base ref have never been released
+pub(super) async fn get_changelog_from_base_ref(
+    client: &HttpClient,
+    repo: &Repo,
+    base_ref: Option<&str>,
+    fetch_config: &FetchConfig,
+    token: Option<&str>,
+) -> anyhow::Result<RepoChangelog> {
+    let Some(base_ref) = base_ref else {
+        let log = get_never_released_log(client, repo, None, fetch_config, token).await?;
+        return Ok(RepoChangelog::NeverReleased(log));
     };
 
-    let commit_log = get_commit_log(repo, &latest_release.tag_name, &repo.head_ref, token)
+    let commit_log = get_commit_log(client, repo, base_ref, repo.head_ref.name(), token)
         .await
         .context("couldn't fetch commits")?;
 
-    Ok(Some(commit_log))
+    Ok(RepoChangelog::Released(commit_log))
+}
+
+// commit logs report the name of the branch used, rather than HEAD
+async fn with_resolved_head_ref(
+    client: &HttpClient,
+    repo: &Repo,
+    token: Option<&str>,
+) -> anyhow::Result<Repo> {
+    let mut repo = repo.clone();
+    if repo.head_ref == HeadRef::DefaultBranch(None) {
+        let default_branch = get_default_branch(client, &repo, token)
+            .await
+            .context("couldn't get the repo's default branch")?;
+        repo.head_ref = HeadRef::DefaultBranch(Some(default_branch));
+    }
+
+    Ok(repo)
 }
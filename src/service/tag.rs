@@ -0,0 +1,152 @@
+use crate::domain::{Tag, TagPattern};
+use semver::Version;
+
+pub(super) fn get_highest_semver_tag<'a>(
+    tags: &'a [Tag],
+    tag_prefix: Option<&str>,
+    tag_pattern: Option<&TagPattern>,
+    consider_prereleases: bool,
+) -> Option<&'a Tag> {
+    tags.iter()
+        .filter(|tag| tag_pattern.is_none_or(|pattern| pattern.is_match(&tag.name)))
+        .filter_map(|tag| {
+            let version = match tag_prefix {
+                Some(prefix) => tag.name.strip_prefix(prefix)?,
+                None => &tag.name,
+            };
+            Some((parse_version(version)?, tag))
+        })
+        .filter(|(version, _)| consider_prereleases || version.pre.is_empty())
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, tag)| tag)
+}
+
+pub(super) fn is_prerelease(tag_name: &str) -> bool {
+    parse_version(tag_name).is_some_and(|version| !version.pre.is_empty())
+}
+
+fn parse_version(tag_name: &str) -> Option<Version> {
+    let version = tag_name.strip_prefix('v').unwrap_or(tag_name);
+    Version::parse(version).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Baseline, RawRepo, Repo};
+
+    fn build_tags(names: &[&str]) -> Vec<Tag> {
+        names
+            .iter()
+            .map(|name| Tag {
+                name: name.to_string(),
+            })
+            .collect()
+    }
+
+    fn get_tag_pattern(pattern: &str) -> TagPattern {
+        let repo = Repo::try_from(RawRepo {
+            repo: Some("owner/repo".to_string()),
+            path: None,
+            forge: None,
+            host: None,
+            head_ref: None,
+            head_refs: None,
+            consider_prereleases: None,
+            api_base_url: None,
+            baseline: Some(Baseline::Tag),
+            tag_pattern: Some(pattern.to_string()),
+            packages: None,
+            label: None,
+        })
+        .expect("repo should've been valid");
+
+        repo.tag_pattern
+            .expect("tag pattern should've been present")
+    }
+
+    #[test]
+    fn highest_tag_is_chosen_by_semver_order() {
+        // GIVEN
+        let tags = build_tags(&["v1.9.0", "v1.10.0", "v1.2.3", "not-a-version"]);
+
+        // WHEN
+        let result = get_highest_semver_tag(&tags, None, None, false);
+
+        // THEN
+        assert_eq!(result.map(|t| t.name.as_str()), Some("v1.10.0"));
+    }
+
+    #[test]
+    fn tags_without_v_prefix_are_considered() {
+        // GIVEN
+        let tags = build_tags(&["v1.2.0", "1.3.0"]);
+
+        // WHEN
+        let result = get_highest_semver_tag(&tags, None, None, false);
+
+        // THEN
+        assert_eq!(result.map(|t| t.name.as_str()), Some("1.3.0"));
+    }
+
+    #[test]
+    fn prerelease_tags_are_skipped_by_default() {
+        // GIVEN
+        let tags = build_tags(&["v1.2.0", "v2.0.0-rc.1"]);
+
+        // WHEN
+        let result = get_highest_semver_tag(&tags, None, None, false);
+
+        // THEN
+        assert_eq!(result.map(|t| t.name.as_str()), Some("v1.2.0"));
+    }
+
+    #[test]
+    fn prerelease_tags_are_considered_if_asked_for() {
+        // GIVEN
+        let tags = build_tags(&["v1.2.0", "v2.0.0-rc.1"]);
+
+        // WHEN
+        let result = get_highest_semver_tag(&tags, None, None, true);
+
+        // THEN
+        assert_eq!(result.map(|t| t.name.as_str()), Some("v2.0.0-rc.1"));
+    }
+
+    #[test]
+    fn tag_pattern_limits_the_tags_considered() {
+        // GIVEN
+        let tags = build_tags(&["v1.4.0", "v2.1.0", "v1.5.0"]);
+        let pattern = get_tag_pattern(r"^v1\.");
+
+        // WHEN
+        let result = get_highest_semver_tag(&tags, None, Some(&pattern), false);
+
+        // THEN
+        assert_eq!(result.map(|t| t.name.as_str()), Some("v1.5.0"));
+    }
+
+    #[test]
+    fn tag_prefix_limits_the_tags_considered() {
+        // GIVEN
+        let tags = build_tags(&["api/v1.4.0", "web/v2.1.3", "api/v1.10.0", "v3.0.0"]);
+
+        // WHEN
+        let result = get_highest_semver_tag(&tags, Some("api/"), None, false);
+
+        // THEN
+        assert_eq!(result.map(|t| t.name.as_str()), Some("api/v1.10.0"));
+    }
+
+    #[test]
+    fn no_tag_is_returned_if_none_are_semver() {
+        // GIVEN
+        let tags = build_tags(&["latest", "nightly"]);
+
+        // WHEN
+        let result = get_highest_semver_tag(&tags, None, None, true);
+
+        // THEN
+        assert!(result.is_none());
+    }
+}
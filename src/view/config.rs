@@ -0,0 +1,137 @@
+use crate::domain::{Config, HeadRef};
+
+// no requests are made in debug mode, so the names of default branches aren't looked up; None
+// stands for a config that couldn't be read
+pub fn render_head_refs(config: Option<&Config>) -> String {
+    let Some(config) = config else {
+        return "head refs:              <UNKNOWN> (couldn't get config)\n".to_string();
+    };
+
+    let mut output = String::from("head refs:\n");
+    for repo in &config.repos {
+        let head_ref = match &repo.head_ref {
+            HeadRef::Configured(name) => format!("{} (configured)", name),
+            HeadRef::DefaultBranch(_) => "the repo's default branch (inferred)".to_string(),
+        };
+        output.push_str(&format!("  {}: {}\n", repo, head_ref));
+    }
+    for org in &config.orgs {
+        output.push_str(&format!(
+            "  {} (org): the default branch of each discovered repo (inferred)\n",
+            org.name
+        ));
+    }
+
+    output
+}
+
+// repos are listed in the order they appear in, with the settings that "report" would use for them
+pub fn render_repo_list(config: &Config) -> String {
+    let mut output = String::new();
+
+    for repo in &config.repos {
+        let mut settings = vec![
+            format!("head_ref: {}", repo.head_ref),
+            format!("baseline: {:?}", repo.baseline).to_lowercase(),
+        ];
+        if let Some(label) = &repo.label {
+            settings.push(format!("label: {}", label));
+        }
+        if let Some(group) = &repo.group {
+            settings.push(format!("group: {}", group));
+        }
+        if repo.consider_prereleases {
+            settings.push("considers prereleases".to_string());
+        }
+        if !repo.packages.is_empty() {
+            settings.push(format!("packages: {}", repo.packages.len()));
+        }
+        output.push_str(&format!("{} ({})\n", repo, settings.join(", ")));
+    }
+
+    for org in &config.orgs {
+        output.push_str(&format!(
+            "{} (org; its repos are discovered when generating a report)\n",
+            org.name
+        ));
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::parse_config;
+    use insta::assert_snapshot;
+
+    #[test]
+    fn head_refs_show_whether_they_are_configured_or_inferred() -> anyhow::Result<()> {
+        // GIVEN
+        let config = parse_config(
+            r#"
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "develop"
+
+[[repos]]
+repo = "owner/repo-b"
+
+[[orgs]]
+name = "owner"
+"#,
+            None,
+            None,
+        )?;
+
+        // WHEN
+        let result = render_head_refs(Some(&config));
+
+        // THEN
+        assert_snapshot!(result, @r"
+        head refs:
+          owner/repo-a: develop (configured)
+          owner/repo-b: the repo's default branch (inferred)
+          owner (org): the default branch of each discovered repo (inferred)
+        ");
+
+        Ok(())
+    }
+
+    #[test]
+    fn repo_list_shows_the_settings_each_repo_is_reported_with() -> anyhow::Result<()> {
+        // GIVEN
+        let config = parse_config(
+            r#"
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "develop"
+consider_prereleases = true
+
+[[groups]]
+name = "payments"
+
+[[groups.repos]]
+repo = "owner/repo-b"
+baseline = "tag"
+
+[[orgs]]
+name = "owner"
+"#,
+            None,
+            None,
+        )?;
+
+        // WHEN
+        let result = render_repo_list(&config);
+
+        // THEN
+        assert_snapshot!(result, @r"
+        owner/repo-a (head_ref: develop, baseline: release, considers prereleases)
+        owner/repo-b (head_ref: default branch, baseline: tag, group: payments)
+        owner (org; its repos are discovered when generating a report)
+        ");
+
+        Ok(())
+    }
+}
@@ -1,38 +1,14 @@
-use crate::domain::CommitLog;
+use super::template_data::TemplateData;
+use crate::domain::{CommitLogResults, RunConfig};
 use anyhow::Context;
 use chrono::{DateTime, Utc};
-use serde::Serialize;
 use tera::Tera;
 
 const BUILT_IN_TEMPLATE: &str = include_str!("assets/template.html");
 
-#[derive(Serialize)]
-struct HtmlData {
-    title: String,
-    timestamp: String,
-    commit_logs: Vec<HtmlCommitLog>,
-}
-
-#[derive(Serialize)]
-struct HtmlCommitLog {
-    repo: String,
-    base_ref: String,
-    head_ref: String,
-    compare_url: Option<String>,
-    commits: Vec<HtmlCommit>,
-}
-
-#[derive(Serialize)]
-struct HtmlCommit {
-    short_sha: String,
-    html_url: String,
-    message: String,
-    author: String,
-    date: String,
-}
-
 pub(super) fn render_output(
-    commit_logs: &[CommitLog],
+    results: &CommitLogResults,
+    run_config: &RunConfig,
     custom_template: Option<&str>,
     title: &str,
     now: DateTime<Utc>,
@@ -48,76 +24,31 @@ pub(super) fn render_output(
             .context("failed to parse built-in HTML template")?,
     }
 
-    let html_data = build_html_data(commit_logs, title, now);
-
-    let mut context = tera::Context::new();
-    context.insert("title", &html_data.title);
-    context.insert("timestamp", &html_data.timestamp);
-    context.insert("commit_logs", &html_data.commit_logs);
+    let mut context = TemplateData::new(results, run_config, now).into_context()?;
+    context.insert("title", title);
 
     tera.render("template.html", &context)
         .context("failed to render HTML template")
 }
 
-fn build_html_data(commit_logs: &[CommitLog], title: &str, now: DateTime<Utc>) -> HtmlData {
-    let html_commit_logs: Vec<HtmlCommitLog> = commit_logs
-        .iter()
-        .map(|log| {
-            let commits: Vec<HtmlCommit> = log
-                .commits
-                .iter()
-                .map(|commit| {
-                    let short_sha = commit.sha.chars().take(7).collect::<String>();
-                    let html_url = commit.html_url.clone();
-                    let message = commit
-                        .commit
-                        .message
-                        .lines()
-                        .next()
-                        .unwrap_or(&commit.commit.message)
-                        .to_string();
-                    let author = commit.commit.author.name.clone();
-                    let date = commit.commit.author.date.format("%b %e, %Y").to_string();
-
-                    HtmlCommit {
-                        short_sha,
-                        html_url,
-                        message,
-                        author,
-                        date,
-                    }
-                })
-                .collect();
-
-            let compare_url = if !commits.is_empty() {
-                Some(log.html_url.clone())
-            } else {
-                None
-            };
-
-            HtmlCommitLog {
-                repo: log.repo.to_string(),
-                base_ref: log.base_ref.to_string(),
-                head_ref: log.head_ref.to_string(),
-                compare_url,
-                commits,
-            }
-        })
-        .collect();
-
-    HtmlData {
-        title: title.to_string(),
-        timestamp: now.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
-        commit_logs: html_commit_logs,
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use super::super::testdata::{TEST_HTML_TEMPLATE, get_test_commit_logs};
+    use super::super::testdata::{
+        TEST_HTML_TEMPLATE, get_test_commit_logs, get_test_never_released_logs, get_test_results,
+        get_test_run_config,
+    };
     use super::*;
+    use crate::domain::{CommitLogFetchErrors, FetchErrorKind, HtmlConfig, OutputType};
     use chrono::TimeZone;
 
+    fn get_html_output_type() -> OutputType {
+        OutputType::Html(HtmlConfig {
+            output_path: "unreleased.html".into(),
+            title: "unreleased".to_string(),
+            template: None,
+        })
+    }
+
     #[test]
     fn built_in_html_template_is_rendered_correctly() {
         // GIVEN
@@ -125,8 +56,14 @@ mod tests {
         let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
 
         // WHEN
-        let html =
-            render_output(&commit_logs, None, "unreleased", now).expect("result should've been Ok");
+        let html = render_output(
+            &get_test_results(commit_logs, vec![], CommitLogFetchErrors::new()),
+            &get_test_run_config(get_html_output_type()),
+            None,
+            "unreleased",
+            now,
+        )
+        .expect("result should've been Ok");
 
         // THEN
         insta::assert_snapshot!(html);
@@ -139,10 +76,146 @@ mod tests {
         let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
 
         // WHEN
-        let html = render_output(&commit_logs, Some(TEST_HTML_TEMPLATE), "unreleased", now)
-            .expect("result should've been Ok");
+        let html = render_output(
+            &get_test_results(commit_logs, vec![], CommitLogFetchErrors::new()),
+            &get_test_run_config(get_html_output_type()),
+            Some(TEST_HTML_TEMPLATE),
+            "unreleased",
+            now,
+        )
+        .expect("result should've been Ok");
 
         // THEN
         insta::assert_snapshot!(html);
     }
+
+    #[test]
+    fn truncated_commit_logs_are_marked_in_built_in_html_template() {
+        // GIVEN
+        let mut commit_logs = get_test_commit_logs();
+        commit_logs[1].total_commits = 1500;
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let html = render_output(
+            &get_test_results(commit_logs, vec![], CommitLogFetchErrors::new()),
+            &get_test_run_config(get_html_output_type()),
+            None,
+            "unreleased",
+            now,
+        )
+        .expect("result should've been Ok");
+
+        // THEN
+        assert!(html.contains("(1500 commits, showing latest 3)"));
+        assert!(html.contains("(1 commit)"));
+    }
+
+    #[test]
+    fn grouped_commit_logs_are_sectioned_in_built_in_html_template() {
+        // GIVEN
+        let mut commit_logs = get_test_commit_logs();
+        commit_logs[1].repo.group = Some("payments".to_string());
+        commit_logs[2].repo.group = Some("payments".to_string());
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let html = render_output(
+            &get_test_results(commit_logs, vec![], CommitLogFetchErrors::new()),
+            &get_test_run_config(get_html_output_type()),
+            None,
+            "unreleased",
+            now,
+        )
+        .expect("result should've been Ok");
+
+        // THEN
+        assert_eq!(html.matches(">payments</h2>").count(), 1);
+        let group_index = html
+            .find(">payments</h2>")
+            .expect("group heading should be present");
+        let app_one_index = html.find("app-one").expect("app-one should be present");
+        let app_two_index = html.find("app-two").expect("app-two should be present");
+        assert!(app_one_index < group_index);
+        assert!(group_index < app_two_index);
+    }
+
+    #[test]
+    fn labelled_commit_logs_are_nested_under_their_repo_in_built_in_html_template() {
+        // GIVEN
+        let mut commit_logs = get_test_commit_logs();
+        commit_logs.truncate(1);
+        let mut release_log = get_test_commit_logs().remove(0);
+        commit_logs[0].repo.label = Some("main".to_string());
+        release_log.repo.label = Some("release/1.x".to_string());
+        release_log.head_ref = "release/1.x".to_string();
+        commit_logs.push(release_log);
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let html = render_output(
+            &get_test_results(commit_logs, vec![], CommitLogFetchErrors::new()),
+            &get_test_run_config(get_html_output_type()),
+            None,
+            "unreleased",
+            now,
+        )
+        .expect("result should've been Ok");
+
+        // THEN
+        assert_eq!(html.matches(r#"pt-2">owner&#x2F;app-one</p>"#).count(), 1);
+        assert_eq!(html.matches(r#"commit-log ml-4"#).count(), 2);
+        assert!(html.contains(r#"mb-2">release&#x2F;1.x"#));
+    }
+
+    #[test]
+    fn never_released_logs_are_rendered_in_built_in_html_template() {
+        // GIVEN
+        let never_released_logs = get_test_never_released_logs();
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let html = render_output(
+            &get_test_results(vec![], never_released_logs, CommitLogFetchErrors::new()),
+            &get_test_run_config(get_html_output_type()),
+            None,
+            "unreleased",
+            now,
+        )
+        .expect("result should've been Ok");
+
+        // THEN
+        insta::assert_snapshot!(html);
+    }
+
+    #[test]
+    fn errors_are_rendered_in_built_in_html_template() {
+        // GIVEN
+        let mut commit_logs = get_test_commit_logs();
+        let failed_log = commit_logs.remove(1);
+        let mut errors = CommitLogFetchErrors::new();
+        errors.add_repo_error(
+            failed_log.repo,
+            FetchErrorKind::Fetch,
+            anyhow::anyhow!("GitHub API request failed with status 404 Not Found")
+                .context("couldn't fetch commit log"),
+        );
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let html = render_output(
+            &get_test_results(commit_logs, vec![], errors),
+            &get_test_run_config(get_html_output_type()),
+            None,
+            "unreleased",
+            now,
+        )
+        .expect("result should've been Ok");
+
+        // THEN
+        assert!(html.contains("Errors"));
+        assert!(html.contains(
+            "couldn&#x27;t fetch commit log: GitHub API request failed with status 404 Not Found"
+        ));
+    }
 }
@@ -0,0 +1,233 @@
+use crate::domain::{Commit, CommitLog, CommitLogFetchErrors, FetchErrorKind, NeverReleasedLog};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+// bump this whenever a field is removed, renamed, or changes its meaning; adding fields is
+// considered backwards compatible
+const JSON_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct JsonOutput {
+    schema_version: u32,
+    generated_at: DateTime<Utc>,
+    commit_logs: Vec<JsonCommitLog>,
+    never_released_logs: Vec<JsonNeverReleasedLog>,
+    errors: Vec<JsonError>,
+}
+
+#[derive(Serialize)]
+struct JsonCommitLog {
+    repo: String,
+    group: Option<String>,
+    label: Option<String>,
+    package: Option<String>,
+    base_ref: String,
+    head_ref: String,
+    compare_url: String,
+    total_commits: usize,
+    ahead_by: usize,
+    behind_by: usize,
+    truncated: bool,
+    total_is_lower_bound: bool,
+    commits: Vec<JsonCommit>,
+}
+
+#[derive(Serialize)]
+struct JsonNeverReleasedLog {
+    repo: String,
+    group: Option<String>,
+    label: Option<String>,
+    package: Option<String>,
+    head_ref: String,
+    commits_url: String,
+    commits: Vec<JsonCommit>,
+}
+
+#[derive(Serialize)]
+struct JsonCommit {
+    sha: String,
+    message: String,
+    author: String,
+    date: DateTime<Utc>,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct JsonError {
+    repo: Option<String>,
+    label: Option<String>,
+    kind: &'static str,
+    message: String,
+}
+
+pub(super) fn render_output(
+    commit_logs: &[CommitLog],
+    never_released_logs: &[NeverReleasedLog],
+    errors: &CommitLogFetchErrors,
+    now: DateTime<Utc>,
+) -> anyhow::Result<String> {
+    let output = JsonOutput {
+        schema_version: JSON_SCHEMA_VERSION,
+        generated_at: now,
+        commit_logs: commit_logs
+            .iter()
+            .map(|log| JsonCommitLog {
+                repo: log.repo.to_string(),
+                group: log.repo.group.clone(),
+                label: log.repo.label.clone(),
+                package: log.package.clone(),
+                base_ref: log.base_ref.clone(),
+                head_ref: log.head_ref.clone(),
+                compare_url: log.html_url.clone(),
+                total_commits: log.total_commits,
+                ahead_by: log.ahead_by,
+                behind_by: log.behind_by,
+                truncated: log.is_truncated(),
+                total_is_lower_bound: log.total_is_lower_bound,
+                commits: build_json_commits(&log.commits),
+            })
+            .collect(),
+        never_released_logs: never_released_logs
+            .iter()
+            .map(|log| JsonNeverReleasedLog {
+                repo: log.repo.to_string(),
+                group: log.repo.group.clone(),
+                label: log.repo.label.clone(),
+                package: log.package.clone(),
+                head_ref: log.head_ref.clone(),
+                commits_url: log.html_url.clone(),
+                commits: build_json_commits(&log.commits),
+            })
+            .collect(),
+        errors: errors
+            .errors()
+            .iter()
+            .map(|error| JsonError {
+                repo: error.repo().map(|repo| repo.to_string()),
+                label: error.repo().and_then(|repo| repo.label.clone()),
+                kind: get_error_kind_name(error.kind()),
+                message: format!("{:#}", error.error()),
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&output).context("failed to serialize report to JSON")
+}
+
+fn get_error_kind_name(kind: FetchErrorKind) -> &'static str {
+    match kind {
+        FetchErrorKind::Fetch => "fetch",
+        FetchErrorKind::Timeout => "timeout",
+        FetchErrorKind::Deadline => "deadline",
+        FetchErrorKind::Discovery => "discovery",
+        FetchErrorKind::Internal => "internal",
+    }
+}
+
+fn build_json_commits(commits: &[Commit]) -> Vec<JsonCommit> {
+    commits
+        .iter()
+        .map(|commit| JsonCommit {
+            sha: commit.sha.clone(),
+            message: commit.commit.message.clone(),
+            author: commit.commit.author.name.clone(),
+            date: commit.commit.author.date,
+            url: commit.html_url.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testdata::{get_test_commit_logs, get_test_never_released_logs};
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn json_output_is_rendered_correctly() {
+        // GIVEN
+        let mut commit_logs = get_test_commit_logs();
+        let failed_log = commit_logs.remove(2);
+        commit_logs.truncate(1);
+        let mut errors = CommitLogFetchErrors::new();
+        errors.add_repo_error(
+            failed_log.repo,
+            FetchErrorKind::Fetch,
+            anyhow::anyhow!("GitHub API request failed with status 404 Not Found")
+                .context("couldn't fetch commit log"),
+        );
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = render_output(&commit_logs, &get_test_never_released_logs(), &errors, now)
+            .expect("result should've been Ok");
+
+        // THEN
+        insta::assert_snapshot!(result, @r#"
+        {
+          "schema_version": 1,
+          "generated_at": "2025-01-16T12:00:00Z",
+          "commit_logs": [
+            {
+              "repo": "owner/app-one",
+              "group": null,
+              "label": null,
+              "package": null,
+              "base_ref": "v1.0.0",
+              "head_ref": "main",
+              "compare_url": "https://github.com/owner/app-one/compare/v1.0.0...main",
+              "total_commits": 1,
+              "ahead_by": 1,
+              "behind_by": 0,
+              "truncated": false,
+              "total_is_lower_bound": false,
+              "commits": [
+                {
+                  "sha": "ae7de14",
+                  "message": "add tracing support",
+                  "author": "User A",
+                  "date": "2025-01-15T10:00:00Z",
+                  "url": "https://github.com/owner/app-one/commit/ae7de14"
+                }
+              ]
+            }
+          ],
+          "never_released_logs": [
+            {
+              "repo": "owner/app-four",
+              "group": null,
+              "label": null,
+              "package": null,
+              "head_ref": "main",
+              "commits_url": "https://github.com/owner/app-four/commits/main",
+              "commits": [
+                {
+                  "sha": "9b1f0c2",
+                  "message": "add readme",
+                  "author": "User C",
+                  "date": "2025-01-16T09:00:00Z",
+                  "url": "https://github.com/owner/app-four/commit/9b1f0c2"
+                },
+                {
+                  "sha": "4e3d2a1",
+                  "message": "initial commit",
+                  "author": "User C",
+                  "date": "2025-01-10T09:00:00Z",
+                  "url": "https://github.com/owner/app-four/commit/4e3d2a1"
+                }
+              ]
+            }
+          ],
+          "errors": [
+            {
+              "repo": "owner/app-three",
+              "label": null,
+              "kind": "fetch",
+              "message": "couldn't fetch commit log: GitHub API request failed with status 404 Not Found"
+            }
+          ]
+        }
+        "#);
+    }
+}
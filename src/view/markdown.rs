@@ -0,0 +1,221 @@
+use super::template_data::TemplateData;
+use crate::domain::{CommitLogResults, RunConfig};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use tera::{Tera, Value};
+
+const BUILT_IN_TEMPLATE: &str = include_str!("assets/template.md");
+// characters that Markdown (or GitHub's flavor of it) could treat as formatting, a link, HTML, or
+// the end of a table cell
+const MARKDOWN_SPECIAL_CHARS: &[char] = &['\\', '`', '*', '_', '[', ']', '<', '>', '|', '~'];
+
+pub(super) fn render_output(
+    results: &CommitLogResults,
+    run_config: &RunConfig,
+    custom_template: Option<&str>,
+    now: DateTime<Utc>,
+) -> anyhow::Result<String> {
+    let mut tera = Tera::default();
+    tera.register_filter("escape_markdown", escape_markdown);
+
+    // templates not ending in .html are not autoescaped by tera, which is what's needed here
+    match custom_template {
+        Some(template) => tera
+            .add_raw_template("template.md", template)
+            .context("failed to parse Markdown template")?,
+        None => tera
+            .add_raw_template("template.md", BUILT_IN_TEMPLATE)
+            .context("failed to parse built-in Markdown template")?,
+    }
+
+    let context = TemplateData::new(results, run_config, now).into_context()?;
+
+    tera.render("template.md", &context)
+        .context("failed to render Markdown template")
+}
+
+// commit messages and authors come from whoever made the commits, so they're shown as is rather
+// than rendered, and can't break out of the table they're in; "#" only starts a heading at the
+// start of a line, and is left alone elsewhere so that references like "#2" still get linked
+fn escape_markdown(value: &Value, _: &HashMap<String, Value>) -> tera::Result<Value> {
+    let text = tera::try_get_value!("escape_markdown", "value", String, value);
+
+    let mut escaped = String::with_capacity(text.len());
+    for (i, c) in text.chars().enumerate() {
+        if MARKDOWN_SPECIAL_CHARS.contains(&c) || (i == 0 && c == '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    Ok(Value::String(escaped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testdata::{
+        TEST_MARKDOWN_TEMPLATE, get_test_commit_logs, get_test_never_released_logs,
+        get_test_results, get_test_run_config,
+    };
+    use super::*;
+    use crate::domain::{CommitLogFetchErrors, FetchErrorKind, MarkdownConfig, OutputType};
+    use chrono::TimeZone;
+
+    fn get_markdown_output_type() -> OutputType {
+        OutputType::Markdown(MarkdownConfig { template: None })
+    }
+
+    #[test]
+    fn built_in_markdown_template_is_rendered_correctly() {
+        // GIVEN
+        let mut commit_logs = get_test_commit_logs();
+        commit_logs[1].commits[1].commit.message = "allow filtering apps | repos".to_string();
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = render_output(
+            &get_test_results(
+                commit_logs,
+                get_test_never_released_logs(),
+                CommitLogFetchErrors::new(),
+            ),
+            &get_test_run_config(get_markdown_output_type()),
+            None,
+            now,
+        )
+        .expect("result should've been Ok");
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        ### owner/app-one
+
+        [`v1.0.0..main`](https://github.com/owner/app-one/compare/v1.0.0...main) (1 commit)
+
+        | Commit | Message | Author | Date |
+        | --- | --- | --- | --- |
+        | [`ae7de14`](https://github.com/owner/app-one/commit/ae7de14) | add tracing support | User A | Jan 15, 2025 |
+
+        ### owner/app-two
+
+        [`v2.0.0..main`](https://github.com/owner/app-two/compare/v2.0.0...main) (3 commits)
+
+        | Commit | Message | Author | Date |
+        | --- | --- | --- | --- |
+        | [`1443d43`](https://github.com/owner/app-two/commit/1443d43) | add cli test for when no versions match app filter | User A | Jan 16, 2025 |
+        | [`c536d77`](https://github.com/owner/app-two/commit/c536d77) | allow filtering apps \| repos | User B | Jan 16, 2025 |
+        | [`2ff3e97`](https://github.com/owner/app-two/commit/2ff3e97) | allow configuring table style (#2) commit | User A | Jan 15, 2025 |
+
+        ### owner/app-three
+
+        `v0.1.0..main` (0 commits)
+
+        ## Never released
+
+        ### owner/app-four
+
+        [`main`](https://github.com/owner/app-four/commits/main) (no releases yet, showing 2 recent commits)
+
+        | Commit | Message | Author | Date |
+        | --- | --- | --- | --- |
+        | [`9b1f0c2`](https://github.com/owner/app-four/commit/9b1f0c2) | add readme | User C | Jan 16, 2025 |
+        | [`4e3d2a1`](https://github.com/owner/app-four/commit/4e3d2a1) | initial commit | User C | Jan 10, 2025 |
+        ");
+    }
+
+    #[test]
+    fn markdown_in_commit_messages_is_not_rendered() {
+        // GIVEN
+        let mut commit_logs = get_test_commit_logs();
+        commit_logs.truncate(1);
+        commit_logs[0].commits[0].commit.message =
+            "# fix `eval` for *all* __inputs__ | see [docs](https://evil.example.com) <img src=x onerror=alert(1)> ~~or not~~ \\ (#2)".to_string();
+        commit_logs[0].commits[0].commit.author.name = "<b>User A</b>".to_string();
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = render_output(
+            &get_test_results(commit_logs, vec![], CommitLogFetchErrors::new()),
+            &get_test_run_config(get_markdown_output_type()),
+            None,
+            now,
+        )
+        .expect("result should've been Ok");
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        ### owner/app-one
+
+        [`v1.0.0..main`](https://github.com/owner/app-one/compare/v1.0.0...main) (1 commit)
+
+        | Commit | Message | Author | Date |
+        | --- | --- | --- | --- |
+        | [`ae7de14`](https://github.com/owner/app-one/commit/ae7de14) | \# fix \`eval\` for \*all\* \_\_inputs\_\_ \| see \[docs\](https://evil.example.com) \<img src=x onerror=alert(1)\> \~\~or not\~\~ \\ (#2) | \<b\>User A\</b\> | Jan 15, 2025 |
+        ");
+    }
+
+    #[test]
+    fn errors_are_rendered_in_built_in_markdown_template() {
+        // GIVEN
+        let mut commit_logs = get_test_commit_logs();
+        let failed_log = commit_logs.remove(1);
+        commit_logs.truncate(1);
+        let mut errors = CommitLogFetchErrors::new();
+        errors.add_repo_error(
+            failed_log.repo,
+            FetchErrorKind::Fetch,
+            anyhow::anyhow!("GitHub API request failed with status 404 Not Found")
+                .context("couldn't fetch commit log"),
+        );
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = render_output(
+            &get_test_results(commit_logs, vec![], errors),
+            &get_test_run_config(get_markdown_output_type()),
+            None,
+            now,
+        )
+        .expect("result should've been Ok");
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        ### owner/app-one
+
+        [`v1.0.0..main`](https://github.com/owner/app-one/compare/v1.0.0...main) (1 commit)
+
+        | Commit | Message | Author | Date |
+        | --- | --- | --- | --- |
+        | [`ae7de14`](https://github.com/owner/app-one/commit/ae7de14) | add tracing support | User A | Jan 15, 2025 |
+
+        ## Errors
+
+        - `owner/app-two`: couldn't fetch commit log: GitHub API request failed with status 404 Not Found
+        ");
+    }
+
+    #[test]
+    fn custom_markdown_template_is_rendered_correctly() {
+        // GIVEN
+        let commit_logs = get_test_commit_logs();
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = render_output(
+            &get_test_results(commit_logs, vec![], CommitLogFetchErrors::new()),
+            &get_test_run_config(get_markdown_output_type()),
+            Some(TEST_MARKDOWN_TEMPLATE),
+            now,
+        )
+        .expect("result should've been Ok");
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        Report generated at 2025-01-16T12:00:00Z
+
+        - owner/app-one: 1 unreleased commit(s) by User A
+        - owner/app-two: 3 unreleased commit(s) by User A, User B, User A
+        - owner/app-three: 0 unreleased commit(s) by
+        ");
+    }
+}
@@ -1,8 +1,14 @@
+mod config;
 mod date;
 mod html;
+mod json;
+mod markdown;
 mod output;
 mod stdout;
+mod template;
+mod template_data;
 #[cfg(test)]
 mod testdata;
 
+pub use config::*;
 pub use output::*;
@@ -1,21 +1,39 @@
-use crate::domain::{CommitLog, OutputType, RunConfig};
+use crate::domain::{CommitLogResults, OutputType, RunConfig};
 use chrono::{DateTime, Utc};
 
 pub fn render_output(
-    commit_logs: &[CommitLog],
+    results: &CommitLogResults,
     config: &RunConfig,
     reference_time: DateTime<Utc>,
 ) -> anyhow::Result<String> {
     let output = match &config.output_type {
-        OutputType::Stdout(config) => {
-            super::stdout::render_output(commit_logs, config.plain_output, reference_time)
-        }
-        OutputType::Html(config) => super::html::render_output(
-            commit_logs,
-            config.template.as_deref(),
-            &config.title,
+        OutputType::Stdout(stdout_config) => super::stdout::render_output(
+            &results.logs,
+            &results.never_released_logs,
+            &results.errors,
+            stdout_config.plain_output,
+            reference_time,
+        ),
+        OutputType::Html(html_config) => super::html::render_output(
+            results,
+            config,
+            html_config.template.as_deref(),
+            &html_config.title,
+            reference_time,
+        )?,
+        OutputType::Json => super::json::render_output(
+            &results.logs,
+            &results.never_released_logs,
+            &results.errors,
+            reference_time,
+        )?,
+        OutputType::Markdown(markdown_config) => super::markdown::render_output(
+            results,
+            config,
+            markdown_config.template.as_deref(),
             reference_time,
         )?,
+        OutputType::Template(_) => super::template::render_output(results, config, reference_time)?,
     };
 
     Ok(output)
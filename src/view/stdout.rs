@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 
 use super::date::get_humanized_date;
-use crate::domain::CommitLog;
+use crate::domain::{Commit, CommitLog, CommitLogFetchErrors, NeverReleasedLog, Repo};
 use comfy_table::{Cell, Color as TableColor, Table, presets};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
@@ -19,69 +19,134 @@ const COMMIT_MESSAGE_MAX_LENGTH: usize = 80;
 
 pub(super) fn render_output(
     logs: &[CommitLog],
+    never_released_logs: &[NeverReleasedLog],
+    errors: &CommitLogFetchErrors,
     plain_output: bool,
     reference_time: DateTime<Utc>,
 ) -> String {
     let mut output = String::new();
+    let mut current_group = None;
+    let mut current_repo = None;
 
     for (i, log) in logs.iter().enumerate() {
-        let commits_word = if log.commits.len() == 1 {
+        push_group_header(&mut output, &mut current_group, log.repo.group.as_deref());
+        let title = get_log_title(&mut output, &mut current_repo, &log.repo);
+
+        let commits_word = if log.total_commits == 1 {
             "commit"
         } else {
             "commits"
         };
 
+        let truncation_marker = if log.is_truncated() {
+            format!(", showing latest {}", log.commits.len())
+        } else {
+            String::new()
+        };
+
+        let lower_bound_marker = if log.total_is_lower_bound {
+            "at least "
+        } else {
+            ""
+        };
+
         output.push_str(&format!(
-            "{}/{} {}..{} ({} {})\n\n",
-            log.repo.owner,
-            log.repo.repo,
+            "{}{} {}..{} ({}{} {}{})\n\n",
+            title,
+            get_package_label(log.package.as_deref()),
             log.base_ref,
             log.head_ref,
-            log.commits.len(),
+            lower_bound_marker,
+            log.total_commits,
             commits_word,
+            truncation_marker,
         ));
 
         if log.commits.is_empty() {
             continue;
         }
 
-        let mut table = Table::new();
-        table.load_preset(presets::NOTHING);
-
-        for commit in &log.commits {
-            let short_sha = &commit.sha[..7.min(commit.sha.len())];
-            let first_line = commit
-                .commit
-                .message
-                .lines()
-                .next()
-                .unwrap_or(&commit.commit.message);
-
-            let truncated_message = truncate_message(first_line, COMMIT_MESSAGE_MAX_LENGTH);
-            let relative_time = get_humanized_date(&commit.commit.author.date, &reference_time);
-
-            if plain_output {
-                table.add_row(vec![
-                    short_sha,
-                    &truncated_message,
-                    &commit.commit.author.name,
-                    &relative_time,
-                ]);
-            } else {
-                let author_color = get_author_color(&commit.commit.author.name);
-                table.add_row(vec![
-                    Cell::new(short_sha).fg(TableColor::Grey),
-                    Cell::new(&truncated_message),
-                    Cell::new(&commit.commit.author.name).fg(author_color),
-                    Cell::new(&relative_time).fg(TableColor::Yellow),
-                ]);
+        output.push_str(&render_commits(&log.commits, plain_output, reference_time));
+        output.push('\n');
+
+        if i < logs.len() - 1 {
+            output.push('\n');
+        }
+    }
+
+    if !never_released_logs.is_empty() {
+        output.push_str(&render_never_released_logs(
+            &output,
+            never_released_logs,
+            plain_output,
+            reference_time,
+        ));
+    }
+
+    if !errors.is_empty() {
+        if !output.is_empty() && !output.ends_with("\n\n") {
+            output.push('\n');
+        }
+        output.push_str("errors\n\n");
+
+        for error in errors.errors() {
+            match error.repo() {
+                Some(repo) => output.push_str(&format!(
+                    " - {}{}: {:#}\n",
+                    repo,
+                    get_repo_label(repo.label.as_deref()),
+                    error.error()
+                )),
+                None => output.push_str(&format!(" - system error: {:#}\n", error.error())),
             }
         }
+    }
 
-        output.push_str(&table.to_string());
+    output
+}
+
+fn render_never_released_logs(
+    preceding_output: &str,
+    never_released_logs: &[NeverReleasedLog],
+    plain_output: bool,
+    reference_time: DateTime<Utc>,
+) -> String {
+    let mut output = String::new();
+
+    if !preceding_output.is_empty() && !preceding_output.ends_with("\n\n") {
         output.push('\n');
+    }
+    output.push_str("never released\n\n");
 
-        if i < logs.len() - 1 {
+    let mut current_group = None;
+    let mut current_repo = None;
+    for (i, log) in never_released_logs.iter().enumerate() {
+        push_group_header(&mut output, &mut current_group, log.repo.group.as_deref());
+        let title = get_log_title(&mut output, &mut current_repo, &log.repo);
+
+        let commits_word = if log.commits.len() == 1 {
+            "commit"
+        } else {
+            "commits"
+        };
+
+        output.push_str(&format!(
+            "{}{} {} (no releases yet, showing {} recent {})\n\n",
+            title,
+            get_package_label(log.package.as_deref()),
+            log.head_ref,
+            log.commits.len(),
+            commits_word,
+        ));
+
+        if log.commits.is_empty() {
+            continue;
+        }
+
+        output.push_str(&render_commits(&log.commits, plain_output, reference_time));
+        output.push('\n');
+
+        if i < never_released_logs.len() - 1 {
             output.push('\n');
         }
     }
@@ -89,6 +154,103 @@ pub(super) fn render_output(
     output
 }
 
+// logs are sorted by group, so a header is added whenever the group changes
+fn push_group_header<'a>(
+    output: &mut String,
+    current_group: &mut Option<&'a str>,
+    group: Option<&'a str>,
+) {
+    let Some(group) = group else {
+        return;
+    };
+
+    if *current_group == Some(group) {
+        return;
+    }
+
+    if !output.is_empty() && !output.ends_with("\n\n") {
+        output.push('\n');
+    }
+    output.push_str(&format!("group: {}\n\n", group));
+    *current_group = Some(group);
+}
+
+// labelled logs (eg. for the several head refs of a repo) are shown as sub-sections under a header
+// for the repo; logs are sorted by repo, so the header is added whenever the repo changes
+fn get_log_title<'a>(
+    output: &mut String,
+    current_repo: &mut Option<&'a Repo>,
+    repo: &'a Repo,
+) -> String {
+    let Some(label) = &repo.label else {
+        *current_repo = None;
+        return repo.to_string();
+    };
+
+    let is_new_repo = current_repo
+        .is_none_or(|current| !current.is_same_repo(repo) || current.group != repo.group);
+    if is_new_repo {
+        if !output.is_empty() && !output.ends_with("\n\n") {
+            output.push('\n');
+        }
+        output.push_str(&format!("{}\n\n", repo));
+        *current_repo = Some(repo);
+    }
+
+    format!("  {}", label)
+}
+
+fn get_repo_label(label: Option<&str>) -> String {
+    match label {
+        Some(label) => format!(" ({})", label),
+        None => String::new(),
+    }
+}
+
+fn get_package_label(package: Option<&str>) -> String {
+    match package {
+        Some(package) => format!(" [{}]", package),
+        None => String::new(),
+    }
+}
+
+fn render_commits(commits: &[Commit], plain_output: bool, reference_time: DateTime<Utc>) -> String {
+    let mut table = Table::new();
+    table.load_preset(presets::NOTHING);
+
+    for commit in commits {
+        let short_sha = &commit.sha[..7.min(commit.sha.len())];
+        let first_line = commit
+            .commit
+            .message
+            .lines()
+            .next()
+            .unwrap_or(&commit.commit.message);
+
+        let truncated_message = truncate_message(first_line, COMMIT_MESSAGE_MAX_LENGTH);
+        let relative_time = get_humanized_date(&commit.commit.author.date, &reference_time);
+
+        if plain_output {
+            table.add_row(vec![
+                short_sha,
+                &truncated_message,
+                &commit.commit.author.name,
+                &relative_time,
+            ]);
+        } else {
+            let author_color = get_author_color(&commit.commit.author.name);
+            table.add_row(vec![
+                Cell::new(short_sha).fg(TableColor::Grey),
+                Cell::new(&truncated_message),
+                Cell::new(&commit.commit.author.name).fg(author_color),
+                Cell::new(&relative_time).fg(TableColor::Yellow),
+            ]);
+        }
+    }
+
+    table.to_string()
+}
+
 fn get_author_color(author_name: &str) -> TableColor {
     let mut hasher = DefaultHasher::new();
     author_name.hash(&mut hasher);
@@ -110,9 +272,11 @@ fn truncate_message(message: &str, max_len: usize) -> String {
 mod tests {
     use std::collections::HashSet;
 
-    use super::super::testdata::get_test_commit_logs;
+    use super::super::testdata::{get_test_commit_logs, get_test_never_released_logs};
     use super::*;
-    use crate::domain::{Author, Commit, CommitDetail, Repo};
+    use crate::domain::{
+        Author, Baseline, Commit, CommitDetail, FetchErrorKind, Forge, HeadRef, Repo,
+    };
     use chrono::TimeZone;
 
     #[test]
@@ -121,7 +285,13 @@ mod tests {
         let reference = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
 
         // WHEN
-        let result = render_output(&get_test_commit_logs(), true, reference);
+        let result = render_output(
+            &get_test_commit_logs(),
+            &[],
+            &CommitLogFetchErrors::new(),
+            true,
+            reference,
+        );
 
         // THEN
         insta::assert_snapshot!(result, @r"
@@ -148,9 +318,17 @@ mod tests {
             repo: Repo {
             owner: "owner".into(),
             repo: "app-one".into(),
-                head_ref: "main".into(),
+            forge: Forge::GitHub,
+                head_ref: HeadRef::Configured("main".into()),
                 consider_prereleases: true,
+                api_base_url: "https://api.github.com".into(),
+                baseline: Baseline::Release,
+                tag_pattern: None,
+                packages: vec![],
+                group: None,
+            label: None,
         },
+            package: None,
             base_ref: "v2.0.0".into(),
             head_ref: "main".into(),
             commits: vec![
@@ -168,10 +346,14 @@ mod tests {
                 },
             ],
             html_url: "https://github.com/org/app-two/compare/v2.0.0...main".to_string(),
+            total_commits: 1,
+            ahead_by: 1,
+            behind_by: 0,
+            total_is_lower_bound: false,
         };
 
         // WHEN
-        let result = render_output(&[log], true, reference);
+        let result = render_output(&[log], &[], &CommitLogFetchErrors::new(), true, reference);
 
         // THEN
         insta::assert_snapshot!(result, @r"
@@ -181,6 +363,188 @@ mod tests {
         ");
     }
 
+    #[test]
+    fn truncated_commit_logs_are_marked() {
+        // GIVEN
+        let reference = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+        let mut logs = get_test_commit_logs();
+        logs.truncate(2);
+        logs[1].total_commits = 1500;
+        logs[1].ahead_by = 1500;
+
+        // WHEN
+        let result = render_output(&logs, &[], &CommitLogFetchErrors::new(), true, reference);
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        owner/app-one v1.0.0..main (1 commit)
+
+         ae7de14  add tracing support  User A  1d ago 
+
+        owner/app-two v2.0.0..main (1500 commits, showing latest 3)
+
+         1443d43  add cli test for when no versions match app filter  User A  30m ago 
+         c536d77  allow filtering apps to run for (#3) commit         User B  1h ago  
+         2ff3e97  allow configuring table style (#2) commit           User A  1d ago  
+        ");
+    }
+
+    #[test]
+    fn package_commit_logs_are_labelled() {
+        // GIVEN
+        let reference = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+        let mut logs = get_test_commit_logs();
+        logs.truncate(1);
+        logs[0].package = Some("api".to_string());
+        logs[0].base_ref = "api/v1.0.0".to_string();
+
+        // WHEN
+        let result = render_output(&logs, &[], &CommitLogFetchErrors::new(), true, reference);
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        owner/app-one [api] api/v1.0.0..main (1 commit)
+
+         ae7de14  add tracing support  User A  1d ago
+        ");
+    }
+
+    #[test]
+    fn grouped_commit_logs_are_sectioned_by_group() {
+        // GIVEN
+        let reference = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+        let mut logs = get_test_commit_logs();
+        logs.remove(1);
+        logs[1].repo.group = Some("payments".to_string());
+        let mut never_released_logs = get_test_never_released_logs();
+        never_released_logs[0].repo.group = Some("platform".to_string());
+
+        // WHEN
+        let result = render_output(
+            &logs,
+            &never_released_logs,
+            &CommitLogFetchErrors::new(),
+            true,
+            reference,
+        );
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        owner/app-one v1.0.0..main (1 commit)
+
+         ae7de14  add tracing support  User A  1d ago 
+
+        group: payments
+
+        owner/app-three v0.1.0..main (0 commits)
+
+        never released
+
+        group: platform
+
+        owner/app-four main (no releases yet, showing 2 recent commits)
+
+         9b1f0c2  add readme      User C  3h ago 
+         4e3d2a1  initial commit  User C  6d ago
+        ");
+    }
+
+    #[test]
+    fn labelled_commit_logs_are_shown_as_sub_sections_of_their_repo() {
+        // GIVEN
+        let reference = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+        let mut logs = get_test_commit_logs();
+        logs.truncate(1);
+        let mut release_log = get_test_commit_logs().remove(0);
+        logs[0].repo.label = Some("main".to_string());
+        release_log.repo.label = Some("release/1.x".to_string());
+        release_log.head_ref = "release/1.x".to_string();
+        logs.push(release_log);
+
+        // WHEN
+        let result = render_output(&logs, &[], &CommitLogFetchErrors::new(), true, reference);
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        owner/app-one
+
+          main v1.0.0..main (1 commit)
+
+         ae7de14  add tracing support  User A  1d ago 
+
+          release/1.x v1.0.0..release/1.x (1 commit)
+
+         ae7de14  add tracing support  User A  1d ago
+        ");
+    }
+
+    #[test]
+    fn never_released_logs_are_rendered_in_their_own_section() {
+        // GIVEN
+        let reference = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = render_output(
+            &get_test_commit_logs(),
+            &get_test_never_released_logs(),
+            &CommitLogFetchErrors::new(),
+            true,
+            reference,
+        );
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        owner/app-one v1.0.0..main (1 commit)
+
+         ae7de14  add tracing support  User A  1d ago 
+
+        owner/app-two v2.0.0..main (3 commits)
+
+         1443d43  add cli test for when no versions match app filter  User A  30m ago 
+         c536d77  allow filtering apps to run for (#3) commit         User B  1h ago  
+         2ff3e97  allow configuring table style (#2) commit           User A  1d ago  
+
+        owner/app-three v0.1.0..main (0 commits)
+
+        never released
+
+        owner/app-four main (no releases yet, showing 2 recent commits)
+
+         9b1f0c2  add readme      User C  3h ago 
+         4e3d2a1  initial commit  User C  6d ago
+        ");
+    }
+
+    #[test]
+    fn errors_are_rendered_in_their_own_section() {
+        // GIVEN
+        let reference = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+        let mut logs = get_test_commit_logs();
+        let failed_log = logs.remove(1);
+        logs.truncate(1);
+        let mut errors = CommitLogFetchErrors::new();
+        errors.add_repo_error(
+            failed_log.repo,
+            FetchErrorKind::Fetch,
+            anyhow::anyhow!("GitHub API request failed with status 404 Not Found")
+                .context("couldn't fetch commit log"),
+        );
+
+        // WHEN
+        let result = render_output(&logs, &[], &errors, true, reference);
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        owner/app-one v1.0.0..main (1 commit)
+
+         ae7de14  add tracing support  User A  1d ago 
+
+        errors
+
+         - owner/app-two: couldn't fetch commit log: GitHub API request failed with status 404 Not Found
+        ");
+    }
+
     #[test]
     fn get_author_color_returns_consistent_color_for_same_author() {
         // GIVEN
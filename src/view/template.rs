@@ -0,0 +1,123 @@
+use super::template_data::TemplateData;
+use crate::domain::{CommitLogResults, OutputType, RunConfig};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use tera::Tera;
+
+pub(super) fn render_output(
+    results: &CommitLogResults,
+    run_config: &RunConfig,
+    now: DateTime<Utc>,
+) -> anyhow::Result<String> {
+    let OutputType::Template(template_config) = &run_config.output_type else {
+        anyhow::bail!("a template can only be rendered for the template output format");
+    };
+    let mut tera = Tera::default();
+
+    // the output can be in any format, so it's not autoescaped (tera only does that for templates
+    // whose names end in .html, .htm, or .xml)
+    tera.add_raw_template("template", &template_config.template)
+        .context("failed to parse template")?;
+
+    let context = TemplateData::new(results, run_config, now).into_context()?;
+
+    tera.render("template", &context)
+        .context("failed to render template")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testdata::{
+        TEST_TEXT_TEMPLATE, get_test_commit_logs, get_test_never_released_logs, get_test_results,
+        get_test_run_config,
+    };
+    use super::*;
+    use crate::domain::{CommitLogFetchErrors, FetchErrorKind, OutputType, TemplateConfig};
+    use chrono::TimeZone;
+
+    #[test]
+    fn template_is_rendered_with_all_context_fields() {
+        // GIVEN
+        let mut commit_logs = get_test_commit_logs();
+        let failed_log = commit_logs.remove(2);
+        let mut errors = CommitLogFetchErrors::new();
+        errors.add_repo_error(
+            failed_log.repo,
+            FetchErrorKind::Fetch,
+            anyhow::anyhow!("GitHub API request failed with status 404 Not Found")
+                .context("couldn't fetch commit log"),
+        );
+        let results = get_test_results(commit_logs, get_test_never_released_logs(), errors);
+        let run_config = get_test_run_config(OutputType::Template(TemplateConfig {
+            template: TEST_TEXT_TEMPLATE.to_string(),
+            output_path: None,
+        }));
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = render_output(&results, &run_config, now).expect("result should've been Ok");
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        *unreleased* (2025-01-16T12:00:00Z)
+        config: format=template filter=^owner/ keep_going=true never_released_max_commits=10 never_released_max_age_days=none
+        fetch: github_api=auto concurrency=20 request_timeout_secs=30 connect_timeout_secs=10 deadline_secs=300
+        summary: 2 repos, 4 unreleased commits, 1 never released, 1 errors
+
+        owner/app-one v1.0.0..main: 1 commit(s) (ahead by 1, behind by 0, truncated: false)
+        authors: User A (1)
+          - ae7de14 add tracing support (User A, Jan 15, 2025) <https://github.com/owner/app-one/commit/ae7de14>
+
+        owner/app-two v2.0.0..main: 3 commit(s) (ahead by 3, behind by 0, truncated: false)
+        authors: User A (2), User B (1)
+          - 1443d43 add cli test for when no versions match app filter (User A, Jan 16, 2025) <https://github.com/owner/app-two/commit/1443d43>
+          - c536d77 allow filtering apps to run for (#3) commit (User B, Jan 16, 2025) <https://github.com/owner/app-two/commit/c536d77>
+          - 2ff3e97 allow configuring table style (#2) commit (User A, Jan 15, 2025) <https://github.com/owner/app-two/commit/2ff3e97>
+
+        owner/app-four main: never released <https://github.com/owner/app-four/commits/main>
+        authors: User C (2)
+
+        error: owner/app-three: couldn't fetch commit log: GitHub API request failed with status 404 Not Found
+        ");
+    }
+
+    #[test]
+    fn template_output_is_not_escaped() {
+        // GIVEN
+        let results = get_test_results(get_test_commit_logs(), vec![], CommitLogFetchErrors::new());
+        let template = "{% for log in commit_logs %}<{{ log.repo }}>\n{% endfor %}";
+        let run_config = get_test_run_config(OutputType::Template(TemplateConfig {
+            template: template.to_string(),
+            output_path: None,
+        }));
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = render_output(&results, &run_config, now).expect("result should've been Ok");
+
+        // THEN
+        insta::assert_snapshot!(result, @r"
+        <owner/app-one>
+        <owner/app-two>
+        <owner/app-three>
+        ");
+    }
+
+    #[test]
+    fn invalid_template_results_in_an_error() {
+        // GIVEN
+        let results = get_test_results(vec![], vec![], CommitLogFetchErrors::new());
+        let template = "{% for log in commit_logs %}";
+        let run_config = get_test_run_config(OutputType::Template(TemplateConfig {
+            template: template.to_string(),
+            output_path: None,
+        }));
+        let now = Utc.with_ymd_and_hms(2025, 1, 16, 12, 0, 0).unwrap();
+
+        // WHEN
+        let result = render_output(&results, &run_config, now);
+
+        // THEN
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,280 @@
+use crate::domain::{
+    Commit, CommitLog, CommitLogFetchErrors, CommitLogResults, NeverReleasedLog, RunConfig,
+};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+// data shared by the template based renderers; the field names are part of the contract with
+// custom templates (and are documented in the README), so they shouldn't be changed
+
+#[derive(Serialize)]
+pub(super) struct TemplateData {
+    timestamp: String,
+    config: RunConfigData,
+    summary: SummaryData,
+    commit_logs: Vec<CommitLogData>,
+    never_released_logs: Vec<NeverReleasedLogData>,
+    errors: Vec<ErrorData>,
+}
+
+#[derive(Serialize)]
+struct RunConfigData {
+    output_format: String,
+    repo_filter: Option<String>,
+    keep_going: bool,
+    never_released_max_commits: usize,
+    never_released_max_age_days: Option<u32>,
+    github_api: String,
+    concurrency: usize,
+    request_timeout_secs: u64,
+    connect_timeout_secs: u64,
+    deadline_secs: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct SummaryData {
+    commit_log_count: usize,
+    unreleased_commit_count: usize,
+    never_released_count: usize,
+    error_count: usize,
+}
+
+#[derive(Serialize)]
+struct CommitLogData {
+    repo: String,
+    group: Option<String>,
+    label: Option<String>,
+    package: Option<String>,
+    base_ref: String,
+    head_ref: String,
+    compare_url: Option<String>,
+    commits: Vec<CommitData>,
+    authors: Vec<AuthorData>,
+    total_commits: usize,
+    ahead_by: usize,
+    behind_by: usize,
+    truncated: bool,
+    total_is_lower_bound: bool,
+}
+
+#[derive(Serialize)]
+struct NeverReleasedLogData {
+    repo: String,
+    group: Option<String>,
+    label: Option<String>,
+    package: Option<String>,
+    head_ref: String,
+    commits_url: String,
+    commits: Vec<CommitData>,
+    authors: Vec<AuthorData>,
+}
+
+#[derive(Serialize)]
+struct ErrorData {
+    repo: Option<String>,
+    label: Option<String>,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct CommitData {
+    short_sha: String,
+    html_url: String,
+    message: String,
+    author: String,
+    date: String,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct AuthorData {
+    name: String,
+    commit_count: usize,
+}
+
+impl TemplateData {
+    pub(super) fn new(
+        results: &CommitLogResults,
+        run_config: &RunConfig,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let commit_logs = build_commit_logs_data(&results.logs);
+        let never_released_logs = build_never_released_logs_data(&results.never_released_logs);
+        let errors = build_errors_data(&results.errors);
+
+        Self {
+            timestamp: now.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            config: RunConfigData {
+                output_format: run_config.output_type.format().to_string(),
+                repo_filter: run_config.repo_filter.clone(),
+                keep_going: run_config.keep_going,
+                never_released_max_commits: run_config.fetch_config.never_released_max_commits,
+                never_released_max_age_days: run_config.fetch_config.never_released_max_age_days,
+                github_api: run_config.fetch_config.github_api.to_string(),
+                concurrency: run_config.fetch_config.concurrency,
+                request_timeout_secs: run_config.timeouts.request.as_secs(),
+                connect_timeout_secs: run_config.timeouts.connect.as_secs(),
+                deadline_secs: run_config.deadline_secs,
+            },
+            summary: SummaryData {
+                commit_log_count: commit_logs.len(),
+                unreleased_commit_count: commit_logs.iter().map(|log| log.total_commits).sum(),
+                never_released_count: never_released_logs.len(),
+                error_count: errors.len(),
+            },
+            commit_logs,
+            never_released_logs,
+            errors,
+        }
+    }
+
+    pub(super) fn into_context(self) -> anyhow::Result<tera::Context> {
+        tera::Context::from_serialize(self).context("failed to build template context")
+    }
+}
+
+fn build_commit_logs_data(commit_logs: &[CommitLog]) -> Vec<CommitLogData> {
+    commit_logs
+        .iter()
+        .map(|log| {
+            let commits = build_commits_data(&log.commits);
+
+            // local repos don't have compare URLs
+            let compare_url = if !commits.is_empty() && !log.html_url.is_empty() {
+                Some(log.html_url.clone())
+            } else {
+                None
+            };
+
+            CommitLogData {
+                repo: log.repo.to_string(),
+                group: log.repo.group.clone(),
+                label: log.repo.label.clone(),
+                package: log.package.clone(),
+                base_ref: log.base_ref.to_string(),
+                head_ref: log.head_ref.to_string(),
+                compare_url,
+                commits,
+                authors: build_authors_data(&log.commits),
+                total_commits: log.total_commits,
+                ahead_by: log.ahead_by,
+                behind_by: log.behind_by,
+                truncated: log.is_truncated(),
+                total_is_lower_bound: log.total_is_lower_bound,
+            }
+        })
+        .collect()
+}
+
+fn build_never_released_logs_data(
+    never_released_logs: &[NeverReleasedLog],
+) -> Vec<NeverReleasedLogData> {
+    never_released_logs
+        .iter()
+        .map(|log| NeverReleasedLogData {
+            repo: log.repo.to_string(),
+            group: log.repo.group.clone(),
+            label: log.repo.label.clone(),
+            package: log.package.clone(),
+            head_ref: log.head_ref.to_string(),
+            commits_url: log.html_url.clone(),
+            commits: build_commits_data(&log.commits),
+            authors: build_authors_data(&log.commits),
+        })
+        .collect()
+}
+
+fn build_errors_data(errors: &CommitLogFetchErrors) -> Vec<ErrorData> {
+    errors
+        .errors()
+        .iter()
+        .map(|error| ErrorData {
+            repo: error.repo().map(|repo| repo.to_string()),
+            label: error.repo().and_then(|repo| repo.label.clone()),
+            message: format!("{:#}", error.error()),
+        })
+        .collect()
+}
+
+fn build_commits_data(commits: &[Commit]) -> Vec<CommitData> {
+    commits
+        .iter()
+        .map(|commit| {
+            let short_sha = commit.sha.chars().take(7).collect::<String>();
+            let html_url = commit.html_url.clone();
+            let message = commit
+                .commit
+                .message
+                .lines()
+                .next()
+                .unwrap_or(&commit.commit.message)
+                .to_string();
+            let author = commit.commit.author.name.clone();
+            let date = commit.commit.author.date.format("%b %e, %Y").to_string();
+
+            CommitData {
+                short_sha,
+                html_url,
+                message,
+                author,
+                date,
+            }
+        })
+        .collect()
+}
+
+// authors are ordered by the number of commits they've made, most first
+fn build_authors_data(commits: &[Commit]) -> Vec<AuthorData> {
+    let mut commit_counts: HashMap<&str, usize> = HashMap::new();
+    for commit in commits {
+        *commit_counts
+            .entry(commit.commit.author.name.as_str())
+            .or_default() += 1;
+    }
+
+    let mut authors: Vec<AuthorData> = commit_counts
+        .into_iter()
+        .map(|(name, commit_count)| AuthorData {
+            name: name.to_string(),
+            commit_count,
+        })
+        .collect();
+    authors.sort_by(|a, b| {
+        b.commit_count
+            .cmp(&a.commit_count)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    authors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testdata::get_test_commit_logs;
+    use super::*;
+
+    #[test]
+    fn authors_are_ordered_by_commit_count() {
+        // GIVEN
+        let commit_logs = get_test_commit_logs();
+
+        // WHEN
+        let result = build_authors_data(&commit_logs[1].commits);
+
+        // THEN
+        assert_eq!(
+            result,
+            vec![
+                AuthorData {
+                    name: "User A".to_string(),
+                    commit_count: 2,
+                },
+                AuthorData {
+                    name: "User B".to_string(),
+                    commit_count: 1,
+                },
+            ]
+        );
+    }
+}
@@ -1,14 +1,27 @@
-use crate::domain::{Author, Commit, CommitDetail, CommitLog, Repo};
+use crate::domain::{
+    Author, Baseline, Commit, CommitDetail, CommitLog, CommitLogFetchErrors, CommitLogResults,
+    FetchConfig, Forge, GitHubApi, HeadRef, NeverReleasedLog, OutputType, Repo, RequestTimeouts,
+    RunConfig,
+};
 use chrono::{TimeZone, Utc};
+use std::time::Duration;
 
 pub(super) fn get_test_commit_logs() -> Vec<CommitLog> {
     let log1 = CommitLog {
         repo: Repo {
             owner: "owner".into(),
             repo: "app-one".into(),
-            head_ref: "main".into(),
+            forge: Forge::GitHub,
+            head_ref: HeadRef::Configured("main".into()),
             consider_prereleases: true,
+            api_base_url: "https://api.github.com".into(),
+            baseline: Baseline::Release,
+            tag_pattern: None,
+            packages: vec![],
+            group: None,
+            label: None,
         },
+        package: None,
         base_ref: "v1.0.0".into(),
         head_ref: "main".into(),
         commits: vec![Commit {
@@ -23,15 +36,27 @@ pub(super) fn get_test_commit_logs() -> Vec<CommitLog> {
             html_url: "https://github.com/owner/app-one/commit/ae7de14".to_string(),
         }],
         html_url: "https://github.com/owner/app-one/compare/v1.0.0...main".to_string(),
+        total_commits: 1,
+        ahead_by: 1,
+        behind_by: 0,
+        total_is_lower_bound: false,
     };
 
     let log2 = CommitLog {
         repo: Repo {
             owner: "owner".into(),
             repo: "app-two".into(),
-            head_ref: "main".into(),
+            forge: Forge::GitHub,
+            head_ref: HeadRef::Configured("main".into()),
             consider_prereleases: true,
+            api_base_url: "https://api.github.com".into(),
+            baseline: Baseline::Release,
+            tag_pattern: None,
+            packages: vec![],
+            group: None,
+            label: None,
         },
+        package: None,
         base_ref: "v2.0.0".into(),
         head_ref: "main".into(),
         commits: vec![
@@ -70,24 +95,87 @@ pub(super) fn get_test_commit_logs() -> Vec<CommitLog> {
             },
         ],
         html_url: "https://github.com/owner/app-two/compare/v2.0.0...main".to_string(),
+        total_commits: 3,
+        ahead_by: 3,
+        behind_by: 0,
+        total_is_lower_bound: false,
     };
 
     let log3 = CommitLog {
         repo: Repo {
             owner: "owner".into(),
             repo: "app-three".into(),
-            head_ref: "main".into(),
+            forge: Forge::GitHub,
+            head_ref: HeadRef::Configured("main".into()),
             consider_prereleases: true,
+            api_base_url: "https://api.github.com".into(),
+            baseline: Baseline::Release,
+            tag_pattern: None,
+            packages: vec![],
+            group: None,
+            label: None,
         },
+        package: None,
         base_ref: "v0.1.0".into(),
         head_ref: "main".into(),
         commits: vec![],
         html_url: "https://github.com/owner/app-three/compare/v0.1.0...main".to_string(),
+        total_commits: 0,
+        ahead_by: 0,
+        behind_by: 0,
+        total_is_lower_bound: false,
     };
 
     vec![log1, log2, log3]
 }
 
+pub(super) fn get_test_never_released_logs() -> Vec<NeverReleasedLog> {
+    let log1 = NeverReleasedLog {
+        repo: Repo {
+            owner: "owner".into(),
+            repo: "app-four".into(),
+            forge: Forge::GitHub,
+            head_ref: HeadRef::Configured("main".into()),
+            consider_prereleases: false,
+            api_base_url: "https://api.github.com".into(),
+            baseline: Baseline::Release,
+            tag_pattern: None,
+            packages: vec![],
+            group: None,
+            label: None,
+        },
+        package: None,
+        head_ref: "main".into(),
+        commits: vec![
+            Commit {
+                sha: "9b1f0c2".to_string(),
+                commit: CommitDetail {
+                    message: "add readme".to_string(),
+                    author: Author {
+                        name: "User C".to_string(),
+                        date: Utc.with_ymd_and_hms(2025, 1, 16, 9, 0, 0).unwrap(),
+                    },
+                },
+                html_url: "https://github.com/owner/app-four/commit/9b1f0c2".to_string(),
+            },
+            Commit {
+                sha: "4e3d2a1".to_string(),
+                commit: CommitDetail {
+                    message: "initial commit".to_string(),
+                    author: Author {
+                        name: "User C".to_string(),
+                        date: Utc.with_ymd_and_hms(2025, 1, 10, 9, 0, 0).unwrap(),
+                    },
+                },
+                html_url: "https://github.com/owner/app-four/commit/4e3d2a1".to_string(),
+            },
+        ],
+        html_url: "https://github.com/owner/app-four/commits/main".to_string(),
+    };
+
+    vec![log1]
+}
+
 pub(super) const TEST_HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
 <html>
 <head>
@@ -101,7 +189,7 @@ pub(super) const TEST_HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
   <h2>Commit Logs</h2>
   {%- for log in commit_logs %}
   <div>
-    <h3>{{ log.repo }}</h3>
+    <h3>{{ log.repo }}{% if log.package %} ({{ log.package }}){% endif %}</h3>
     <p>{{ log.base_ref }}..{{ log.head_ref }}</p>
     {%- if log.compare_url %}
     <p>Compare: <a href="{{ log.compare_url }}">{{ log.compare_url }}</a></p>
@@ -122,3 +210,61 @@ pub(super) const TEST_HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
 </body>
 </html>
 "#;
+
+pub(super) const TEST_MARKDOWN_TEMPLATE: &str = r#"Report generated at {{ timestamp }}
+{% for log in commit_logs %}
+- {{ log.repo }}: {{ log.total_commits }} unreleased commit(s) by {% for commit in log.commits %}{{ commit.author }}{% if not loop.last %}, {% endif %}{% endfor %}
+{%- endfor %}
+"#;
+
+pub(super) fn get_test_results(
+    logs: Vec<CommitLog>,
+    never_released_logs: Vec<NeverReleasedLog>,
+    errors: CommitLogFetchErrors,
+) -> CommitLogResults {
+    CommitLogResults {
+        logs,
+        never_released_logs,
+        errors,
+    }
+}
+
+pub(super) fn get_test_run_config(output_type: OutputType) -> RunConfig {
+    RunConfig {
+        output_type,
+        repo_filter: Some("^owner/".to_string()),
+        keep_going: true,
+        fetch_config: FetchConfig {
+            never_released_max_commits: 10,
+            github_api: GitHubApi::Auto,
+            concurrency: 20,
+            deadline: None,
+            never_released_max_age_days: None,
+        },
+        timeouts: RequestTimeouts {
+            request: Duration::from_secs(30),
+            connect: Duration::from_secs(10),
+        },
+        deadline_secs: Some(300),
+    }
+}
+
+pub(super) const TEST_TEXT_TEMPLATE: &str = r#"*unreleased* ({{ timestamp }})
+config: format={{ config.output_format }} filter={{ config.repo_filter }} keep_going={{ config.keep_going }} never_released_max_commits={{ config.never_released_max_commits }} never_released_max_age_days={{ config.never_released_max_age_days | default(value="none") }}
+fetch: github_api={{ config.github_api }} concurrency={{ config.concurrency }} request_timeout_secs={{ config.request_timeout_secs }} connect_timeout_secs={{ config.connect_timeout_secs }} deadline_secs={{ config.deadline_secs | default(value="none") }}
+summary: {{ summary.commit_log_count }} repos, {{ summary.unreleased_commit_count }} unreleased commits, {{ summary.never_released_count }} never released, {{ summary.error_count }} errors
+{% for log in commit_logs %}
+{{ log.repo }} {{ log.base_ref }}..{{ log.head_ref }}: {{ log.total_commits }} commit(s) (ahead by {{ log.ahead_by }}, behind by {{ log.behind_by }}, truncated: {{ log.truncated }})
+authors: {% for author in log.authors %}{{ author.name }} ({{ author.commit_count }}){% if not loop.last %}, {% endif %}{% endfor %}
+{%- for commit in log.commits %}
+  - {{ commit.short_sha }} {{ commit.message }} ({{ commit.author }}, {{ commit.date }}) <{{ commit.html_url }}>
+{%- endfor %}
+{% endfor %}
+{%- for log in never_released_logs %}
+{{ log.repo }} {{ log.head_ref }}: never released <{{ log.commits_url }}>
+authors: {% for author in log.authors %}{{ author.name }} ({{ author.commit_count }}){% if not loop.last %}, {% endif %}{% endfor %}
+{% endfor %}
+{%- for error in errors %}
+error: {{ error.repo }}: {{ error.message }}
+{%- endfor %}
+"#;
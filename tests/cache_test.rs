@@ -0,0 +1,131 @@
+mod common;
+
+use common::Fixture;
+use insta_cmd::assert_cmd_snapshot;
+
+#[test]
+fn clear_removes_cached_responses() {
+    // GIVEN
+    let fx = Fixture::new();
+    let cache_dir = fx.create_dir("responses");
+    fx.write_file("responses/0123456789abcdef.json", "{}");
+    let mut cmd = fx.cmd([
+        "cache",
+        "clear",
+        "--cache-dir",
+        &cache_dir.to_string_lossy(),
+    ]);
+
+    // WHEN
+    // THEN
+    let cache_dir_pattern = regex::escape(&cache_dir.to_string_lossy());
+    insta::with_settings!({filters => vec![(cache_dir_pattern.as_str(), "[CACHE_DIR]")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        Cache cleared: [CACHE_DIR]
+
+        ----- stderr -----
+        ");
+    });
+    assert!(!cache_dir.exists());
+}
+
+#[test]
+fn clear_works_when_nothing_is_cached() {
+    // GIVEN
+    let fx = Fixture::new();
+    let cache_dir = fx.path("responses");
+    let mut cmd = fx.cmd([
+        "cache",
+        "clear",
+        "--cache-dir",
+        &cache_dir.to_string_lossy(),
+    ]);
+
+    // WHEN
+    // THEN
+    let cache_dir_pattern = regex::escape(&cache_dir.to_string_lossy());
+    insta::with_settings!({filters => vec![(cache_dir_pattern.as_str(), "[CACHE_DIR]")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        Cache is already empty: [CACHE_DIR]
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn clear_uses_the_default_cache_dir() {
+    // GIVEN
+    let fx = Fixture::new();
+    fx.create_dir("cache/unreleased");
+    fx.write_file("cache/unreleased/0123456789abcdef.json", "{}");
+    let mut cmd = fx.cmd(["cache", "clear"]);
+
+    // WHEN
+    // THEN
+    let cache_home_pattern = regex::escape(&fx.path("cache").to_string_lossy());
+    insta::with_settings!({filters => vec![(cache_home_pattern.as_str(), "[XDG_CACHE_HOME]")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        Cache cleared: [XDG_CACHE_HOME]/unreleased
+
+        ----- stderr -----
+        ");
+    });
+    assert!(!fx.path("cache/unreleased").exists());
+}
+
+#[test]
+fn clear_only_removes_cached_responses() {
+    // GIVEN
+    let fx = Fixture::new();
+    let cache_dir = fx.create_dir("responses");
+    fx.write_file("responses/0123456789abcdef.json", "{}");
+    fx.write_file("responses/0123456789abcdef.1a2b3c.tmp", "{}");
+    fx.write_file("responses/notes.json", "{}");
+    fx.write_file("responses/README.md", "keep me");
+    fx.create_dir("responses/0123456789abcdef.json.d");
+    let mut cmd = fx.cmd([
+        "cache",
+        "clear",
+        "--cache-dir",
+        &cache_dir.to_string_lossy(),
+    ]);
+
+    // WHEN
+    // THEN
+    let cache_dir_pattern = regex::escape(&cache_dir.to_string_lossy());
+    insta::with_settings!({filters => vec![(cache_dir_pattern.as_str(), "[CACHE_DIR]")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        Cache cleared: [CACHE_DIR]
+
+        ----- stderr -----
+        ");
+    });
+    let mut remaining = std::fs::read_dir(&cache_dir)
+        .expect("cache dir should've been left in place")
+        .map(|entry| {
+            entry
+                .expect("entry should've been readable")
+                .file_name()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    remaining.sort();
+    assert_eq!(
+        remaining,
+        vec!["0123456789abcdef.json.d", "README.md", "notes.json"]
+    );
+}
@@ -1,16 +1,26 @@
+// not every test binary uses every helper
+#![allow(dead_code)]
+
 use insta_cmd::get_cargo_bin;
-use std::{ffi::OsStr, path::PathBuf, process::Command};
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tempfile::TempDir;
 
 pub struct Fixture {
     bin_path: PathBuf,
+    temp_dir: TempDir,
 }
 
 #[cfg(test)]
 impl Fixture {
     pub fn new() -> Self {
         let bin_path = get_cargo_bin("unreleased");
+        let temp_dir = tempfile::tempdir().expect("temporary directory should've been created");
 
-        Self { bin_path }
+        Self { bin_path, temp_dir }
     }
 
     pub fn cmd<I, S>(&self, args: I) -> Command
@@ -22,6 +32,89 @@ impl Fixture {
         command.args(args);
         // Set an invalid token to ensure tests don't use real credentials
         command.env("UNRELEASED_GH_TOKEN", "invalid");
+        command.env("UNRELEASED_GITLAB_TOKEN", "invalid");
+        command.env("UNRELEASED_GITEA_TOKEN", "invalid");
+        // keeps API responses from being cached outside the fixture
+        command.env("XDG_CACHE_HOME", self.temp_dir.path().join("cache"));
         command
     }
+
+    pub fn create_dir(&self, name: &str) -> PathBuf {
+        let path = self.temp_dir.path().join(name);
+        std::fs::create_dir_all(&path).expect("directory should've been created");
+        path
+    }
+
+    pub fn write_file(&self, name: &str, contents: &str) -> PathBuf {
+        let path = self.temp_dir.path().join(name);
+        std::fs::write(&path, contents).expect("file should've been written");
+        path
+    }
+
+    pub fn read_file(&self, path: &Path) -> String {
+        std::fs::read_to_string(path).expect("file should've been read")
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.temp_dir.path().join(name)
+    }
+
+    pub fn git(&self, repo_path: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(repo_path)
+            .args(args)
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            // keeps commit SHAs stable across runs
+            .env("GIT_COMMITTER_NAME", "unreleased")
+            .env("GIT_COMMITTER_EMAIL", "unreleased@example.com")
+            .env("GIT_COMMITTER_DATE", "2025-01-16T12:00:00Z")
+            .output()
+            .expect("git should've run");
+        assert!(
+            output.status.success(),
+            "git failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    pub fn git_commit(&self, repo_path: &Path, message: &str, author: &str, date: &str) {
+        let author = format!(
+            "{} <{}@example.com>",
+            author,
+            author.to_lowercase().replace(' ', ".")
+        );
+        let date = format!("--date={}", date);
+        self.git(
+            repo_path,
+            &[
+                "commit",
+                "--allow-empty",
+                "--message",
+                message,
+                "--author",
+                &author,
+                &date,
+            ],
+        );
+    }
+}
+
+// forge-wide tokens are only sent to the forges' default hosts, so requests to a mock server are
+// only authenticated via its host specific token
+pub fn host_token_env_var(server_url: &str) -> String {
+    let host: String = server_url
+        .trim_start_matches("http://")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("UNRELEASED_TOKEN_{}", host)
 }
@@ -0,0 +1,453 @@
+mod common;
+
+use common::Fixture;
+use insta::assert_snapshot;
+use insta_cmd::assert_cmd_snapshot;
+
+const CONFIG: &str = r#"# repos I maintain
+[defaults]
+head_ref = "develop"
+
+[[repos]]
+repo = "owner/repo-a"  # the main one
+
+[[repos]]
+repo = "owner/repo-b"
+head_ref = "main"
+consider_prereleases = true
+
+[[groups]]
+name = "payments"
+baseline = "tag"
+
+[[groups.repos]]
+repo = "owner/repo-c"
+"#;
+
+//-------------//
+//  SUCCESSES  //
+//-------------//
+
+#[test]
+fn init_writes_a_valid_starter_config() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.path("nested/unreleased.toml");
+    let config_path_str = config_path.to_string_lossy().to_string();
+    let mut init_cmd = fx.cmd(["config", "init", "--config-path", &config_path_str]);
+    let mut validate_cmd = fx.cmd(["config", "validate", "--config-path", &config_path_str]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(regex::escape(&config_path_str).as_str(), "[CONFIG_PATH]")]}, {
+        assert_cmd_snapshot!(init_cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        Config written to: [CONFIG_PATH]
+
+        ----- stderr -----
+        ");
+        assert_cmd_snapshot!(validate_cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        config is valid (1 repo(s), 0 org(s))
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn validate_reports_config_without_errors() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.write_file("unreleased.toml", CONFIG);
+    let mut cmd = fx.cmd([
+        "config",
+        "validate",
+        "--config-path",
+        &config_path.to_string_lossy(),
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    config is valid (3 repo(s), 0 org(s))
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn add_appends_a_repo_and_keeps_comments() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.write_file("unreleased.toml", CONFIG);
+    let config_path_str = config_path.to_string_lossy().to_string();
+    let mut cmd = fx.cmd([
+        "config",
+        "add",
+        "owner/repo-d",
+        "--head-ref",
+        "trunk",
+        "--config-path",
+        &config_path_str,
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(regex::escape(&config_path_str).as_str(), "[CONFIG_PATH]")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        Added owner/repo-d to: [CONFIG_PATH]
+
+        ----- stderr -----
+        ");
+    });
+    assert_snapshot!(fx.read_file(&config_path), @r#"
+    # repos I maintain
+    [defaults]
+    head_ref = "develop"
+
+    [[repos]]
+    repo = "owner/repo-a"  # the main one
+
+    [[repos]]
+    repo = "owner/repo-b"
+    head_ref = "main"
+    consider_prereleases = true
+
+    [[repos]]
+    repo = "owner/repo-d"
+    head_ref = "trunk"
+
+    [[groups]]
+    name = "payments"
+    baseline = "tag"
+
+    [[groups.repos]]
+    repo = "owner/repo-c"
+    "#);
+}
+
+#[test]
+fn remove_deletes_a_repo_and_keeps_comments() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.write_file("unreleased.toml", CONFIG);
+    let config_path_str = config_path.to_string_lossy().to_string();
+    let mut cmd = fx.cmd([
+        "config",
+        "remove",
+        "owner/repo-b",
+        "--config-path",
+        &config_path_str,
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(regex::escape(&config_path_str).as_str(), "[CONFIG_PATH]")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        Removed owner/repo-b from: [CONFIG_PATH]
+
+        ----- stderr -----
+        ");
+    });
+    assert_snapshot!(fx.read_file(&config_path), @r#"
+    # repos I maintain
+    [defaults]
+    head_ref = "develop"
+
+    [[repos]]
+    repo = "owner/repo-a"  # the main one
+
+    [[groups]]
+    name = "payments"
+    baseline = "tag"
+
+    [[groups.repos]]
+    repo = "owner/repo-c"
+    "#);
+}
+
+#[test]
+fn list_shows_repos_with_resolved_settings() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.write_file("unreleased.toml", CONFIG);
+    let mut cmd = fx.cmd([
+        "config",
+        "list",
+        "--config-path",
+        &config_path.to_string_lossy(),
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    owner/repo-a (head_ref: develop, baseline: release)
+    owner/repo-b (head_ref: main, baseline: release, considers prereleases)
+    owner/repo-c (head_ref: develop, baseline: tag, group: payments)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn list_can_be_filtered() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.write_file("unreleased.toml", CONFIG);
+    let mut cmd = fx.cmd([
+        "config",
+        "list",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--filter",
+        "payments",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    owner/repo-c (head_ref: develop, baseline: tag, group: payments)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn add_allows_listing_a_repo_again_with_another_head_ref() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.write_file("unreleased.toml", CONFIG);
+    let config_path_str = config_path.to_string_lossy().to_string();
+    let mut add_cmd = fx.cmd([
+        "config",
+        "add",
+        "owner/repo-b",
+        "--head-ref",
+        "release/1.x",
+        "--config-path",
+        &config_path_str,
+    ]);
+    let mut list_cmd = fx.cmd(["config", "list", "--config-path", &config_path_str]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(regex::escape(&config_path_str).as_str(), "[CONFIG_PATH]")]}, {
+        assert_cmd_snapshot!(add_cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        Added owner/repo-b to: [CONFIG_PATH]
+
+        ----- stderr -----
+        ");
+        assert_cmd_snapshot!(list_cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a (head_ref: develop, baseline: release)
+        owner/repo-b (head_ref: main, baseline: release, label: main, considers prereleases)
+        owner/repo-b (head_ref: release/1.x, baseline: release, label: release/1.x)
+        owner/repo-c (head_ref: develop, baseline: tag, group: payments)
+
+        ----- stderr -----
+        ");
+    });
+}
+
+//------------//
+//  FAILURES  //
+//------------//
+
+#[test]
+fn init_fails_if_config_already_exists() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.write_file("unreleased.toml", CONFIG);
+    let config_path_str = config_path.to_string_lossy().to_string();
+    let mut cmd = fx.cmd(["config", "init", "--config-path", &config_path_str]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(regex::escape(&config_path_str).as_str(), "[CONFIG_PATH]")]}, {
+        assert_cmd_snapshot!(cmd, @r#"
+        success: false
+        exit_code: 1
+        ----- stdout -----
+
+        ----- stderr -----
+        Error: couldn't write config to file "[CONFIG_PATH]"
+
+        Caused by:
+            file already exists; use --force to overwrite it
+        "#);
+    });
+    assert_eq!(fx.read_file(&config_path), CONFIG);
+}
+
+#[test]
+fn validate_shows_all_errors() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd([
+        "config",
+        "validate",
+        "--config-path",
+        "tests/assets/invalid-data.toml",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Error: couldn't get config from file "tests/assets/invalid-data.toml"
+
+    Caused by:
+        config has errors:
+         - repo #1 has errors (tests/assets/invalid-data.toml, line 4, column 1):
+             |
+           4 | [[repos]]
+             | ^^^^^^^^^
+           - repo needs to be in the format "owner/repo"
+         - repo #2 has errors (tests/assets/invalid-data.toml, line 7, column 1):
+             |
+           7 | [[repos]]
+             | ^^^^^^^^^
+           - repo name is empty
+         - repo #3 has errors (tests/assets/invalid-data.toml, line 10, column 1):
+              |
+           10 | [[repos]]
+              | ^^^^^^^^^
+           - owner is empty
+         - repo #4 has errors (tests/assets/invalid-data.toml, line 13, column 1):
+              |
+           13 | [[repos]]
+              | ^^^^^^^^^
+           - repo needs to be in the format "owner/repo"
+           - head_ref is empty
+    "#);
+}
+
+#[test]
+fn add_fails_for_a_repo_that_is_already_present() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.write_file("unreleased.toml", CONFIG);
+    let config_path_str = config_path.to_string_lossy().to_string();
+    let mut cmd = fx.cmd([
+        "config",
+        "add",
+        "owner/repo-c",
+        "--config-path",
+        &config_path_str,
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(regex::escape(&config_path_str).as_str(), "[CONFIG_PATH]")]}, {
+        assert_cmd_snapshot!(cmd, @r#"
+        success: false
+        exit_code: 1
+        ----- stdout -----
+
+        ----- stderr -----
+        Error: couldn't add repo to config file "[CONFIG_PATH]"
+
+        Caused by:
+            0: the updated config would be invalid
+            1: config has errors:
+                - group #0 repo #0 has errors ([CONFIG_PATH], line 20, column 1):
+                     |
+                  20 | [[groups.repos]]
+                     | ^^^^^^^^^^^^^^^^
+                  - repo is already listed with the same head_ref
+        "#);
+    });
+    assert_eq!(fx.read_file(&config_path), CONFIG);
+}
+
+#[test]
+fn add_fails_for_an_invalid_repo() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.write_file("unreleased.toml", CONFIG);
+    let config_path_str = config_path.to_string_lossy().to_string();
+    let mut cmd = fx.cmd(["config", "add", "owner/", "--config-path", &config_path_str]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(regex::escape(&config_path_str).as_str(), "[CONFIG_PATH]")]}, {
+        assert_cmd_snapshot!(cmd, @r#"
+        success: false
+        exit_code: 1
+        ----- stdout -----
+
+        ----- stderr -----
+        Error: couldn't add repo to config file "[CONFIG_PATH]"
+
+        Caused by:
+            0: the updated config would be invalid
+            1: config has errors:
+                - repo #2 has errors ([CONFIG_PATH], line 13, column 1):
+                     |
+                  13 | [[repos]]
+                     | ^^^^^^^^^
+                  - repo name is empty
+        "#);
+    });
+    assert_eq!(fx.read_file(&config_path), CONFIG);
+}
+
+#[test]
+fn remove_fails_for_a_repo_that_is_not_present() {
+    // GIVEN
+    let fx = Fixture::new();
+    let config_path = fx.write_file("unreleased.toml", CONFIG);
+    let config_path_str = config_path.to_string_lossy().to_string();
+    let mut cmd = fx.cmd([
+        "config",
+        "remove",
+        "owner/repo-x",
+        "--config-path",
+        &config_path_str,
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(regex::escape(&config_path_str).as_str(), "[CONFIG_PATH]")]}, {
+        assert_cmd_snapshot!(cmd, @r#"
+        success: false
+        exit_code: 1
+        ----- stdout -----
+
+        ----- stderr -----
+        Error: couldn't remove repo from config file "[CONFIG_PATH]"
+
+        Caused by:
+            repo "owner/repo-x" is not present in the config
+        "#);
+    });
+}
@@ -1,122 +1,3003 @@
 mod common;
 
-use common::Fixture;
+use common::{Fixture, host_token_env_var};
 use insta_cmd::assert_cmd_snapshot;
+use mockito::Matcher;
+
+const COMMITS_RESPONSE: &str = r#"[
+  {
+    "sha": "9b1f0c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b",
+    "html_url": "https://github.com/owner/repo-a/commit/9b1f0c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b",
+    "commit": {
+      "message": "add readme",
+      "author": { "name": "User C", "date": "2025-01-16T09:00:00Z" }
+    }
+  }
+]"#;
+
+const GITLAB_COMMITS_RESPONSE: &str = r#"[
+  {
+    "id": "2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e",
+    "message": "allow configuring table style\n\nsome more details",
+    "author_name": "User B",
+    "authored_date": "2025-01-16T11:00:00Z",
+    "web_url": "https://gitlab.example.com/group/repo-a/-/commit/2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e"
+  },
+  {
+    "id": "ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5",
+    "message": "add tracing support",
+    "author_name": "User A",
+    "authored_date": "2025-01-15T10:00:00Z",
+    "web_url": "https://gitlab.example.com/group/repo-a/-/commit/ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5"
+  }
+]"#;
+
+const COMPARE_RESPONSE: &str = r#"{
+  "html_url": "https://github.example.com/owner/repo-a/compare/v1.0.0...main",
+  "total_commits": 2,
+  "ahead_by": 2,
+  "behind_by": 0,
+  "commits": [
+    {
+      "sha": "ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5",
+      "html_url": "https://github.example.com/owner/repo-a/commit/ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5",
+      "commit": {
+        "message": "add tracing support",
+        "author": { "name": "User A", "date": "2025-01-15T10:00:00Z" }
+      }
+    },
+    {
+      "sha": "2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e",
+      "html_url": "https://github.example.com/owner/repo-a/commit/2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e",
+      "commit": {
+        "message": "allow configuring table style\n\nsome more details",
+        "author": { "name": "User B", "date": "2025-01-16T11:00:00Z" }
+      }
+    }
+  ]
+}"#;
 
 //-------------//
 //  SUCCESSES  //
 //-------------//
 
 #[test]
-fn shows_help() {
+fn shows_help() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["report", "--help"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show unreleased commits for repos
+
+    Usage: unreleased report [OPTIONS]
+
+    Options:
+      -c, --config-path <PATH>            Path to the unreleased's file (defaults to <YOUR_CONFIG_DIR>/unreleased/unreleased.toml)
+      -f, --filter <REGEX>                Regex to use for filtering repos
+      -o, --output-format <FORMAT>        Output format [default: stdout] [possible values: stdout, html, json, markdown, template]
+          --debug                         Output debug information without doing anything
+          --stdout-plain                  Whether to output text to stdout without color
+          --html-output <PATH>            Path for the HTML output file [default: unreleased.html]
+          --html-title <STRING>           Title for HTML report [default: unreleased]
+          --html-template <PATH>          Path to custom HTML template file
+          --markdown-template <PATH>      Path to custom Markdown template file
+          --template <PATH>               Path to the template file to render (required for the template output format)
+          --template-output <PATH>        Path for the output file of the template output format (defaults to stdout)
+          --never-released-commits <NUM>  Maximum number of commits to show for repos that have never been released [default: 10]
+          --never-released-days <DAYS>    Only show commits from the last N days for repos that have never been released
+          --keep-going                    Generate a report even if commit logs couldn't be fetched for some repos (exits with code 3 if that happens)
+          --no-cache                      Don't use cached API responses, or cache new ones
+          --cache-dir <PATH>              Directory to cache API responses in (defaults to <YOUR_CACHE_DIR>/unreleased)
+          --github-api <API>              API to fetch GitHub repos with (overrides the config's github_api; defaults to auto) [possible values: auto, rest, graphql]
+          --concurrency <NUM>             Maximum number of repos to fetch at the same time (overrides the config's concurrency; defaults to 20)
+          --request-timeout <SECS>        Seconds to wait for a response before retrying a request (overrides the config's request_timeout_secs; defaults to 30)
+          --connect-timeout <SECS>        Seconds to wait for a connection to a forge (overrides the config's connect_timeout_secs; defaults to 10)
+          --deadline <SECS>               Seconds after which to stop fetching, and report repos not fetched by then as timed out (overrides the config's deadline_secs)
+      -q, --quiet                         Don't show progress, or the remaining API quotas, on stderr (errors are still shown)
+          --progress                      Show progress on stderr even if it isn't a terminal (it's shown on terminals by default)
+      -h, --help                          Print help
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn debug_flag_works_for_defaults() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["report", "--debug"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO
+
+    command:                report
+    config file path:       <NOT PROVIDED>
+    repo filter:            <NOT PROVIDED>
+    never released commits: 10
+    never released days:    <NOT PROVIDED>
+    keep going:             false
+    no cache:               false
+    cache dir:              <NOT PROVIDED>
+    github api:             <NOT PROVIDED>
+    concurrency:            <NOT PROVIDED>
+    request timeout:        <NOT PROVIDED>
+    connect timeout:        <NOT PROVIDED>
+    deadline:               <NOT PROVIDED>
+    progress:               auto
+    output format:          stdout
+    plain output:           false
+
+    head refs:              <UNKNOWN> (couldn't get config)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn debug_flag_works_with_overridden_flags_for_stdout_output() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        "tests/assets/valid-config.toml",
+        "--debug",
+        "--filter",
+        "repo-(a|b)",
+        "--output-format",
+        "stdout",
+        "--stdout-plain",
+        "--github-api",
+        "graphql",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO
+
+    command:                report
+    config file path:       tests/assets/valid-config.toml
+    repo filter:            repo-(a|b)
+    never released commits: 10
+    never released days:    <NOT PROVIDED>
+    keep going:             false
+    no cache:               false
+    cache dir:              <NOT PROVIDED>
+    github api:             graphql
+    concurrency:            <NOT PROVIDED>
+    request timeout:        <NOT PROVIDED>
+    connect timeout:        <NOT PROVIDED>
+    deadline:               <NOT PROVIDED>
+    progress:               auto
+    output format:          stdout
+    plain output:           true
+
+    head refs:
+      owner/repo-a: the repo's default branch (inferred)
+      owner/repo-b: branch (configured)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn debug_flag_works_with_overridden_flags_for_html_output() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        "tests/assets/valid-config.toml",
+        "--debug",
+        "--filter",
+        "repo-(a|b)",
+        "--output-format",
+        "html",
+        "--html-output",
+        "output.html",
+        "--html-template",
+        "tests/assets/template.html",
+        "--html-title",
+        "unreleased code",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    DEBUG INFO
+
+    command:                report
+    config file path:       tests/assets/valid-config.toml
+    repo filter:            repo-(a|b)
+    never released commits: 10
+    never released days:    <NOT PROVIDED>
+    keep going:             false
+    no cache:               false
+    cache dir:              <NOT PROVIDED>
+    github api:             <NOT PROVIDED>
+    concurrency:            <NOT PROVIDED>
+    request timeout:        <NOT PROVIDED>
+    connect timeout:        <NOT PROVIDED>
+    deadline:               <NOT PROVIDED>
+    progress:               auto
+    output format:          html
+    output path:            output.html
+    title:                  unreleased code
+    template path:          tests/assets/template.html
+
+    head refs:
+      owner/repo-a: the repo's default branch (inferred)
+      owner/repo-b: branch (configured)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn report_is_fetched_from_the_configured_api_base_url() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/api/v3/repos/owner/repo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _release_mock = server
+        .mock("GET", "/api/v3/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/api/v3/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}/api/v3"
+
+[[repos]]
+repo = "owner/repo-a"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn report_uses_the_default_branch_of_repos_without_a_head_ref() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/repos/owner/repo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "trunk"}"#)
+        .create();
+    let _release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...trunk")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..trunk (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn report_fails_if_the_default_branch_of_a_repo_cannot_be_determined() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/repos/owner/repo-a")
+        .with_status(404)
+        .with_body(r#"{"message": "Not Found"}"#)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Error: couldn't fetch commit logs for some repos:
+     - owner/repo-a: couldn't get the repo's default branch: GitHub API request failed with status 404 Not Found: {"message": "Not Found"}
+    "#);
+}
+
+#[test]
+fn report_walks_pages_of_releases_until_one_that_is_not_a_draft() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let first_page_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases")
+        .match_query(Matcher::Exact("per_page=100".into()))
+        .with_header("content-type", "application/json")
+        .with_header(
+            "link",
+            &format!(
+                r#"<{}/repos/owner/repo-a/releases?per_page=100&page=2>; rel="next""#,
+                server.url()
+            ),
+        )
+        .with_body(
+            r#"[
+  {"tag_name": "v1.2.0", "draft": true, "prerelease": false},
+  {"tag_name": "v1.2.0-rc.1", "draft": true, "prerelease": true}
+]"#,
+        )
+        .expect(1)
+        .create();
+    let second_page_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases")
+        .match_query(Matcher::Exact("per_page=100&page=2".into()))
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"[
+  {"tag_name": "v1.1.0-rc.1", "draft": false, "prerelease": true},
+  {"tag_name": "v1.0.0", "draft": false, "prerelease": false}
+]"#,
+        )
+        .expect(1)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.1.0-rc.1...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+consider_prereleases = true
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.1.0-rc.1..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+    first_page_mock.assert();
+    second_page_mock.assert();
+}
+
+#[test]
+fn repos_with_only_draft_releases_are_shown_as_never_released() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _releases_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases")
+        .match_query(Matcher::Exact("per_page=100".into()))
+        .with_header("content-type", "application/json")
+        .with_body(r#"[{"tag_name": "v1.0.0", "draft": true, "prerelease": false}]"#)
+        .create();
+    let _commits_mock = server
+        .mock("GET", "/repos/owner/repo-a/commits")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("sha".into(), "main".into()),
+            Matcher::UrlEncoded("per_page".into(), "1".into()),
+        ]))
+        .with_header("content-type", "application/json")
+        .with_body(COMMITS_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+consider_prereleases = true
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--never-released-commits",
+        "1",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        never released
+
+        owner/repo-a main (no releases yet, showing 1 recent commit)
+
+         9b1f0c2  add readme  User C  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn repos_without_a_latest_release_are_shown_as_never_released() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_status(404)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"message": "Not Found"}"#)
+        .expect(1)
+        .create();
+    // GitHub's latest release already excludes drafts and prereleases, so the full list of
+    // releases isn't needed
+    let releases_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases")
+        .match_query(Matcher::Any)
+        .expect(0)
+        .create();
+    let _commits_mock = server
+        .mock("GET", "/repos/owner/repo-a/commits")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("sha".into(), "main".into()),
+            Matcher::UrlEncoded("per_page".into(), "1".into()),
+        ]))
+        .with_header("content-type", "application/json")
+        .with_body(COMMITS_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--never-released-commits",
+        "1",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        never released
+
+        owner/repo-a main (no releases yet, showing 1 recent commit)
+
+         9b1f0c2  add readme  User C  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+    release_mock.assert();
+    releases_mock.assert();
+}
+
+#[test]
+fn report_retries_requests_that_fail_with_a_server_error() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let failing_release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_status(502)
+        .expect(1)
+        .create();
+    let release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .expect(1)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+    failing_release_mock.assert();
+    release_mock.assert();
+}
+
+#[test]
+fn report_waits_out_rate_limits_and_shows_the_remaining_quota() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let rate_limited_release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_status(403)
+        .with_header("retry-after", "1")
+        .with_body(r#"{"message": "You have exceeded a secondary rate limit"}"#)
+        .expect(1)
+        .create();
+    let release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_header("x-ratelimit-limit", "5000")
+        .with_header("x-ratelimit-remaining", "4998")
+        .with_header("x-ratelimit-reset", "1737028860")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .expect(1)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_header("x-ratelimit-limit", "5000")
+        .with_header("x-ratelimit-remaining", "4997")
+        .with_header("x-ratelimit-reset", "1737028860")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![
+        (r"\d+d ago", "[N]d ago"),
+        (r"127\.0\.0\.1:\d+", "[HOST]"),
+    ]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        API quota for [HOST]: 4997/5000 requests remaining, resets at 2025-01-16 12:01:00 UTC
+        ");
+    });
+    rate_limited_release_mock.assert();
+    release_mock.assert();
+}
+
+#[test]
+fn report_retries_requests_that_time_out() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _hung_release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body_from_request(|_| {
+            std::thread::sleep(std::time::Duration::from_secs(2));
+            br#"{"tag_name": "v1.0.0", "draft": false}"#.to_vec()
+        })
+        .expect(1)
+        .create();
+    let _release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--request-timeout",
+        "1",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn report_lists_repos_not_fetched_before_the_deadline_as_timed_out() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _release_a_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+    // repo-b is hosted elsewhere, so that its hung request doesn't hold up the ones for repo-a
+    let mut hung_server = mockito::Server::new();
+    let _hung_release_b_mock = hung_server
+        .mock("GET", "/repos/owner/repo-b/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body_from_request(|_| {
+            std::thread::sleep(std::time::Duration::from_secs(3));
+            br#"{"tag_name": "v1.0.0", "draft": false}"#.to_vec()
+        })
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+keep_going = true
+deadline_secs = 1
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+
+[[repos]]
+repo = "owner/repo-b"
+head_ref = "main"
+api_base_url = "{}"
+"#,
+            server.url(),
+            hung_server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: false
+        exit_code: 3
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+        errors
+
+         - owner/repo-b: timed out; the deadline passed before its changelog was fetched
+
+
+        ----- stderr -----
+        couldn't fetch commit logs for some repos:
+         - owner/repo-b: timed out; the deadline passed before its changelog was fetched
+        ");
+    });
+}
+
+#[test]
+fn json_reports_tell_repos_not_fetched_before_the_deadline_apart() {
+    // GIVEN
+    let mut hung_server = mockito::Server::new();
+    let _hung_release_mock = hung_server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body_from_request(|_| {
+            std::thread::sleep(std::time::Duration::from_secs(3));
+            br#"{"tag_name": "v1.0.0", "draft": false}"#.to_vec()
+        })
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+keep_going = true
+deadline_secs = 1
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+"#,
+            hung_server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--output-format",
+        "json",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r#""generated_at": "[^"]+""#, r#""generated_at": "[TIMESTAMP]""#)]}, {
+        assert_cmd_snapshot!(cmd, @r#"
+        success: false
+        exit_code: 3
+        ----- stdout -----
+        {
+          "schema_version": 1,
+          "generated_at": "[TIMESTAMP]",
+          "commit_logs": [],
+          "never_released_logs": [],
+          "errors": [
+            {
+              "repo": "owner/repo-a",
+              "label": null,
+              "kind": "deadline",
+              "message": "timed out; the deadline passed before its changelog was fetched"
+            }
+          ]
+        }
+
+        ----- stderr -----
+        couldn't fetch commit logs for some repos:
+         - owner/repo-a: timed out; the deadline passed before its changelog was fetched
+        "#);
+    });
+}
+
+#[test]
+fn report_makes_conditional_requests_for_cached_responses() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_header("etag", r#""release-etag""#)
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_header("last-modified", "Thu, 16 Jan 2025 12:00:00 GMT")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+"#,
+            server.url()
+        ),
+    );
+    let args = [
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ];
+    let first_run = fx.cmd(args).output().expect("command should've run");
+    assert!(first_run.status.success());
+
+    let not_modified_release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .match_header("if-none-match", r#""release-etag""#)
+        .with_status(304)
+        .create();
+    let not_modified_compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .match_header("if-modified-since", "Thu, 16 Jan 2025 12:00:00 GMT")
+        .with_status(304)
+        .create();
+    let mut cmd = fx.cmd(args);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+    not_modified_release_mock.assert();
+    not_modified_compare_mock.assert();
+}
+
+#[test]
+fn responses_cached_for_one_token_are_not_used_with_another() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_header("etag", r#""release-etag""#)
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+"#,
+            server.url()
+        ),
+    );
+    let args = [
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ];
+    let token_env_var = host_token_env_var(&server.url());
+    let first_run = fx
+        .cmd(args)
+        .env(&token_env_var, "token-a")
+        .output()
+        .expect("command should've run");
+    assert!(first_run.status.success());
+
+    let unconditional_release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .match_header("authorization", "Bearer token-b")
+        .match_header("if-none-match", Matcher::Missing)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+
+    // WHEN
+    let second_run = fx
+        .cmd(args)
+        .env(&token_env_var, "token-b")
+        .output()
+        .expect("command should've run");
+
+    // THEN
+    assert!(second_run.status.success());
+    unconditional_release_mock.assert();
+}
+
+#[test]
+fn report_skips_the_cache_when_asked_to() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .match_header("if-none-match", Matcher::Missing)
+        .with_header("content-type", "application/json")
+        .with_header("etag", r#""release-etag""#)
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .expect(2)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let cache_dir = fx.path("responses");
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+"#,
+            server.url()
+        ),
+    );
+    let first_run = fx
+        .cmd([
+            "report",
+            "--config-path",
+            &config_path.to_string_lossy(),
+            "--cache-dir",
+            &cache_dir.to_string_lossy(),
+        ])
+        .output()
+        .expect("command should've run");
+    assert!(first_run.status.success());
+    assert!(cache_dir.exists());
+
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--no-cache",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+    release_mock.assert();
+}
+
+#[test]
+fn report_batches_lookups_for_github_repos_via_graphql_when_asked_to() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _snapshots_mock = server
+        .mock("POST", "/graphql")
+        .match_header("authorization", "Bearer invalid")
+        .match_body(Matcher::Regex("defaultBranchRef".into()))
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+  "data": {
+    "r0": {
+      "defaultBranchRef": { "name": "main" },
+      "latestRelease": { "tagName": "v1.0.0", "isDraft": false }
+    },
+    "r1": {
+      "defaultBranchRef": { "name": "main" },
+      "refs": {
+        "nodes": [{ "name": "v1.2.0" }, { "name": "v1.10.0" }],
+        "pageInfo": { "hasNextPage": false }
+      }
+    },
+    "r2": {
+      "defaultBranchRef": { "name": "main" },
+      "latestRelease": null
+    }
+  }
+}"#,
+        )
+        .expect(1)
+        .create();
+    let _comparisons_mock = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::Regex("compare".into()))
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+  "data": {
+    "r0": {
+      "ref": {
+        "compare": {
+          "aheadBy": 2,
+          "behindBy": 0,
+          "commits": {
+            "totalCount": 2,
+            "nodes": [
+              {
+                "oid": "ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5",
+                "url": "https://github.com/owner/repo-a/commit/ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5",
+                "message": "add tracing support",
+                "committedDate": "2025-01-15T10:00:00Z",
+                "author": { "name": "User A", "date": "2025-01-15T10:00:00Z" }
+              },
+              {
+                "oid": "2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e",
+                "url": "https://github.com/owner/repo-a/commit/2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e",
+                "message": "allow configuring table style\n\nsome more details",
+                "committedDate": "2025-01-16T11:00:00Z",
+                "author": { "name": "User B", "date": "2025-01-16T11:00:00Z" }
+              }
+            ]
+          }
+        }
+      }
+    },
+    "r1": {
+      "ref": {
+        "compare": {
+          "aheadBy": 150,
+          "behindBy": 0,
+          "commits": { "totalCount": 150, "nodes": [] }
+        }
+      }
+    }
+  }
+}"#,
+        )
+        .expect(1)
+        .create();
+    // logs with more commits than fit in a query, and recent commits of repos that have never
+    // been released, are fetched via REST
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-b/compare/v1.10.0...develop")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+    let _commits_mock = server
+        .mock("GET", "/repos/owner/repo-c/commits")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("sha".into(), "main".into()),
+            Matcher::UrlEncoded("per_page".into(), "1".into()),
+        ]))
+        .with_header("content-type", "application/json")
+        .with_body(COMMITS_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+
+[[repos]]
+repo = "owner/repo-b"
+head_ref = "develop"
+baseline = "tag"
+
+[[repos]]
+repo = "owner/repo-c"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--never-released-commits",
+        "1",
+        "--github-api",
+        "graphql",
+    ]);
+    cmd.env(host_token_env_var(&server.url()), "invalid");
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+        owner/repo-b v1.10.0..develop (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+        never released
+
+        owner/repo-c main (no releases yet, showing 1 recent commit)
+
+         9b1f0c2  add readme  User C  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn graphql_batches_fall_back_to_rest_within_the_concurrency_limit() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _snapshots_mock = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::Regex("defaultBranchRef".into()))
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+  "data": {
+    "r0": {
+      "defaultBranchRef": { "name": "main" },
+      "latestRelease": null
+    }
+  }
+}"#,
+        )
+        .expect(1)
+        .create();
+    let _commits_mock = server
+        .mock("GET", "/repos/owner/repo-c/commits")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("sha".into(), "main".into()),
+            Matcher::UrlEncoded("per_page".into(), "1".into()),
+        ]))
+        .with_header("content-type", "application/json")
+        .with_body(COMMITS_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-c"
+"#,
+            server.url()
+        ),
+    );
+    // the REST lookup needs a slot of its own, which only frees up once the batch's queries are
+    // made; the deadline keeps the test from hanging if it never does
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--never-released-commits",
+        "1",
+        "--github-api",
+        "graphql",
+        "--concurrency",
+        "1",
+        "--deadline",
+        "10",
+    ]);
+    cmd.env(host_token_env_var(&server.url()), "invalid");
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        never released
+
+        owner/repo-c main (no releases yet, showing 1 recent commit)
+
+         9b1f0c2  add readme  User C  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn github_api_can_be_chosen_via_config() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _snapshots_mock = server
+        .mock("POST", "/graphql")
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+  "data": {
+    "r0": {
+      "defaultBranchRef": { "name": "main" },
+      "latestRelease": null
+    }
+  }
+}"#,
+        )
+        .create();
+    let _commits_mock = server
+        .mock("GET", "/repos/owner/repo-a/commits")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMMITS_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+github_api = "graphql"
+
+[[repos]]
+repo = "owner/repo-a"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--never-released-commits",
+        "1",
+    ]);
+    cmd.env(host_token_env_var(&server.url()), "invalid");
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        never released
+
+        owner/repo-a main (no releases yet, showing 1 recent commit)
+
+         9b1f0c2  add readme  User C  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn repos_that_graphql_lookups_fail_for_are_reported_as_errors() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _snapshots_mock = server
+        .mock("POST", "/graphql")
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+  "data": {
+    "r0": {
+      "defaultBranchRef": { "name": "main" },
+      "latestRelease": null
+    },
+    "r1": null
+  },
+  "errors": [
+    {
+      "type": "NOT_FOUND",
+      "path": ["r1"],
+      "message": "Could not resolve to a Repository with the name 'owner/repo-b'."
+    }
+  ]
+}"#,
+        )
+        .create();
+    let _commits_mock = server
+        .mock("GET", "/repos/owner/repo-a/commits")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMMITS_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+
+[[repos]]
+repo = "owner/repo-b"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--never-released-commits",
+        "1",
+        "--github-api",
+        "graphql",
+        "--keep-going",
+    ]);
+    cmd.env(host_token_env_var(&server.url()), "invalid");
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: false
+        exit_code: 3
+        ----- stdout -----
+        never released
+
+        owner/repo-a main (no releases yet, showing 1 recent commit)
+
+         9b1f0c2  add readme  User C  [N]d ago 
+
+        errors
+
+         - owner/repo-b: couldn't look up the repo via GitHub's GraphQL API: Could not resolve to a Repository with the name 'owner/repo-b'.
+
+
+        ----- stderr -----
+        couldn't fetch commit logs for some repos:
+         - owner/repo-b: couldn't look up the repo via GitHub's GraphQL API: Could not resolve to a Repository with the name 'owner/repo-b'.
+        ");
+    });
+}
+
+#[test]
+fn report_is_fetched_from_gitlab() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/api/v4/projects/group%2Frepo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _releases_mock = server
+        .mock("GET", "/api/v4/projects/group%2Frepo-a/releases")
+        .match_query(Matcher::Any)
+        .match_header("PRIVATE-TOKEN", "invalid")
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"[
+  {"tag_name": "v1.1.0", "upcoming_release": true},
+  {"tag_name": "v1.1.0-rc.1", "upcoming_release": false},
+  {"tag_name": "v1.0.0", "upcoming_release": false}
+]"#,
+        )
+        .create();
+    let _commits_mock = server
+        .mock("GET", "/api/v4/projects/group%2Frepo-a/repository/commits")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("ref_name".into(), "v1.0.0..main".into()),
+            Matcher::UrlEncoded("per_page".into(), "100".into()),
+        ]))
+        .match_header("PRIVATE-TOKEN", "invalid")
+        .with_header("content-type", "application/json")
+        .with_header("x-total", "2")
+        .with_body(GITLAB_COMMITS_RESPONSE)
+        .create();
+    let _behind_mock = server
+        .mock("GET", "/api/v4/projects/group%2Frepo-a/repository/commits")
+        .match_query(Matcher::UrlEncoded(
+            "ref_name".into(),
+            "main..v1.0.0".into(),
+        ))
+        .with_header("content-type", "application/json")
+        .with_header("x-total", "0")
+        .with_body("[]")
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+[[repos]]
+repo = "group/repo-a"
+forge = "gitlab"
+host = "{}"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+    cmd.env(host_token_env_var(&server.url()), "invalid");
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        group/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn gitlab_commit_logs_are_counted_page_by_page_when_the_total_is_not_reported() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/api/v4/projects/group%2Frepo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _releases_mock = server
+        .mock("GET", "/api/v4/projects/group%2Frepo-a/releases")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(r#"[{"tag_name": "v1.0.0", "upcoming_release": false}]"#)
+        .create();
+    let second_page_url = format!(
+        "{}/api/v4/projects/group%2Frepo-a/repository/commits?ref_name=v1.0.0..main&per_page=100&page=2",
+        server.url()
+    );
+    let _first_page_mock = server
+        .mock("GET", "/api/v4/projects/group%2Frepo-a/repository/commits")
+        .match_query(Matcher::Exact(
+            "ref_name=v1.0.0..main&per_page=100".to_string(),
+        ))
+        .with_header("content-type", "application/json")
+        .with_header("link", &format!(r#"<{}>; rel="next""#, second_page_url))
+        .with_body(GITLAB_COMMITS_RESPONSE)
+        .create();
+    let _second_page_mock = server
+        .mock("GET", "/api/v4/projects/group%2Frepo-a/repository/commits")
+        .match_query(Matcher::Exact(
+            "ref_name=v1.0.0..main&per_page=100&page=2".to_string(),
+        ))
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"[
+  {
+    "id": "0a1b2c3d4e5f60718293a4b5c6d7e8f901234567",
+    "message": "initial commit",
+    "author_name": "User A",
+    "authored_date": "2025-01-14T09:00:00Z",
+    "web_url": "https://gitlab.example.com/group/repo-a/-/commit/0a1b2c3d4e5f60718293a4b5c6d7e8f901234567"
+  }
+]"#,
+        )
+        .create();
+    let _behind_mock = server
+        .mock("GET", "/api/v4/projects/group%2Frepo-a/repository/commits")
+        .match_query(Matcher::UrlEncoded(
+            "ref_name".into(),
+            "main..v1.0.0".into(),
+        ))
+        .with_header("content-type", "application/json")
+        .with_body(r#"[{"id": "9f8e7d6c5b4a39281706f5e4d3c2b1a098765432"}]"#)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+[[repos]]
+repo = "group/repo-a"
+forge = "gitlab"
+host = "{}"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--output-format",
+        "json",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![
+        (r#""generated_at": "[^"]+""#, r#""generated_at": "[TIMESTAMP]""#),
+        (r"127\.0\.0\.1:\d+", "[HOST]"),
+    ]}, {
+        assert_cmd_snapshot!(cmd, @r#"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        {
+          "schema_version": 1,
+          "generated_at": "[TIMESTAMP]",
+          "commit_logs": [
+            {
+              "repo": "group/repo-a",
+              "group": null,
+              "label": null,
+              "package": null,
+              "base_ref": "v1.0.0",
+              "head_ref": "main",
+              "compare_url": "http://[HOST]/group/repo-a/-/compare/v1.0.0...main",
+              "total_commits": 3,
+              "ahead_by": 3,
+              "behind_by": 1,
+              "truncated": false,
+              "total_is_lower_bound": false,
+              "commits": [
+                {
+                  "sha": "2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e",
+                  "message": "allow configuring table style\n\nsome more details",
+                  "author": "User B",
+                  "date": "2025-01-16T11:00:00Z",
+                  "url": "https://gitlab.example.com/group/repo-a/-/commit/2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e"
+                },
+                {
+                  "sha": "ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5",
+                  "message": "add tracing support",
+                  "author": "User A",
+                  "date": "2025-01-15T10:00:00Z",
+                  "url": "https://gitlab.example.com/group/repo-a/-/commit/ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5"
+                },
+                {
+                  "sha": "0a1b2c3d4e5f60718293a4b5c6d7e8f901234567",
+                  "message": "initial commit",
+                  "author": "User A",
+                  "date": "2025-01-14T09:00:00Z",
+                  "url": "https://gitlab.example.com/group/repo-a/-/commit/0a1b2c3d4e5f60718293a4b5c6d7e8f901234567"
+                }
+              ]
+            }
+          ],
+          "never_released_logs": [],
+          "errors": []
+        }
+
+        ----- stderr -----
+        "#);
+    });
+}
+
+#[test]
+fn report_is_fetched_from_gitea_with_a_host_specific_token() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/api/v1/repos/owner/repo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _releases_mock = server
+        .mock("GET", "/api/v1/repos/owner/repo-a/releases")
+        .match_query(Matcher::Any)
+        .match_header("Authorization", "token host-token")
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"[
+  {"tag_name": "v1.1.0", "draft": true, "prerelease": false},
+  {"tag_name": "v1.1.0-rc.1", "draft": false, "prerelease": true},
+  {"tag_name": "v1.0.0", "draft": false, "prerelease": false}
+]"#,
+        )
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/api/v1/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_header("Authorization", "token host-token")
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+  "total_commits": 2,
+  "commits": [
+    {
+      "sha": "2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e",
+      "html_url": "https://git.example.com/owner/repo-a/commit/2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e",
+      "commit": {
+        "message": "allow configuring table style\n\nsome more details",
+        "author": { "name": "User B", "email": "b@example.com", "date": "2025-01-16T11:00:00Z" }
+      }
+    },
+    {
+      "sha": "ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5",
+      "html_url": "https://git.example.com/owner/repo-a/commit/ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5",
+      "commit": {
+        "message": "add tracing support",
+        "author": { "name": "User A", "email": "a@example.com", "date": "2025-01-15T10:00:00Z" }
+      }
+    }
+  ]
+}"#,
+        )
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+[[repos]]
+repo = "owner/repo-a"
+forge = "forgejo"
+host = "{}"
+"#,
+            server.url()
+        ),
+    );
+    let host_token_env_var = format!(
+        "UNRELEASED_TOKEN_{}",
+        server.host_with_port().replace(['.', ':'], "_")
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+    cmd.env("UNRELEASED_GITEA_TOKEN", "forge-token");
+    cmd.env(host_token_env_var, "host-token");
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn report_is_generated_for_local_repos() {
+    // GIVEN
+    let fx = Fixture::new();
+    let repo_path = fx.create_dir("repo-a");
+    fx.git(&repo_path, &["init", "--initial-branch", "main"]);
+    fx.git_commit(
+        &repo_path,
+        "initial commit",
+        "User C",
+        "2025-01-10T09:00:00Z",
+    );
+    fx.git(&repo_path, &["tag", "v1.0.0"]);
+    fx.git_commit(
+        &repo_path,
+        "add tracing support",
+        "User A",
+        "2025-01-15T10:00:00Z",
+    );
+    fx.git_commit(
+        &repo_path,
+        "allow configuring table style\n\nsome more details",
+        "User B",
+        "2025-01-16T11:00:00Z",
+    );
+    fx.git(&repo_path, &["tag", "nightly"]);
+
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+[[repos]]
+path = "{}"
+"#,
+            repo_path.to_string_lossy()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    let repo_path_pattern = regex::escape(&repo_path.to_string_lossy());
+    insta::with_settings!({filters => vec![
+        (r"\d+d ago", "[N]d ago"),
+        (repo_path_pattern.as_str(), "[REPO_PATH]"),
+    ]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        [REPO_PATH] v1.0.0..main (2 commits)
+
+         efd7c11  allow configuring table style  User B  [N]d ago 
+         8a02ab9  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn head_refs_of_local_repos_are_not_passed_to_git_as_options() {
+    // GIVEN
+    let fx = Fixture::new();
+    let repo_path = fx.create_dir("repo-a");
+    fx.git(&repo_path, &["init", "--initial-branch", "main"]);
+    fx.git_commit(
+        &repo_path,
+        "initial commit",
+        "User C",
+        "2025-01-10T09:00:00Z",
+    );
+    let injected_path = fx.path("injected");
+
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+[[repos]]
+path = "{}"
+head_ref = "--output={}"
+"#,
+            repo_path.to_string_lossy(),
+            injected_path.to_string_lossy()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    let output = cmd.output().expect("command should've run");
+
+    // THEN
+    assert!(!output.status.success());
+    assert!(!injected_path.exists());
+}
+
+#[test]
+fn report_includes_repos_discovered_from_orgs() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _org_repos_mock = server
+        .mock("GET", "/orgs/owner/repos")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"[
+  {"name": "repo-a", "full_name": "owner/repo-a", "topics": ["service"], "visibility": "public", "archived": false, "fork": false, "default_branch": "main"},
+  {"name": "repo-b", "full_name": "owner/repo-b", "topics": ["service"], "visibility": "public", "archived": true, "fork": false, "default_branch": "main"},
+  {"name": "repo-c", "full_name": "owner/repo-c", "topics": ["docs"], "visibility": "public", "archived": false, "fork": false, "default_branch": "main"},
+  {"name": "repo-d", "full_name": "owner/repo-d", "topics": ["service"], "visibility": "public", "archived": false, "fork": false, "default_branch": "trunk"}
+]"#,
+        )
+        .create();
+    let _release_a_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_a_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...develop")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+    let _release_d_mock = server
+        .mock("GET", "/repos/owner/repo-d/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v0.2.0", "draft": false}"#)
+        .create();
+    let _compare_d_mock = server
+        .mock("GET", "/repos/owner/repo-d/compare/v0.2.0...trunk")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "develop"
+
+[[orgs]]
+name = "owner"
+topics = ["service"]
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..develop (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+        owner/repo-d v0.2.0..trunk (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn report_keeps_going_when_an_org_cant_be_discovered() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _org_repos_mock = server
+        .mock("GET", "/orgs/owner/repos")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"[
+  {"name": "repo-a", "full_name": "owner/repo-a", "topics": [], "visibility": "public", "archived": false, "fork": false, "default_branch": "main"}
+]"#,
+        )
+        .create();
+    let _renamed_org_repos_mock = server
+        .mock("GET", "/orgs/renamed/repos")
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"message": "Not Found"}"#)
+        .create();
+    let _release_a_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_a_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[orgs]]
+name = "renamed"
+
+[[orgs]]
+name = "owner"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--keep-going",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r#"
+        success: false
+        exit_code: 3
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+        errors
+
+         - system error: couldn't discover repos for "renamed": GitHub API request failed with status 404 Not Found: {"message": "Not Found"}
+
+
+        ----- stderr -----
+        couldn't fetch commit logs for some repos:
+         - system error: couldn't discover repos for "renamed": GitHub API request failed with status 404 Not Found: {"message": "Not Found"}
+        "#);
+    });
+}
+
+#[test]
+fn discovered_repos_fall_back_to_the_default_head_ref() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _org_repos_mock = server
+        .mock("GET", "/orgs/owner/repos")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"[
+  {"name": "repo-a", "full_name": "owner/repo-a", "topics": [], "visibility": "public", "archived": false, "fork": false, "default_branch": "main"}
+]"#,
+        )
+        .create();
+    let _release_a_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_a_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...develop")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[defaults]
+head_ref = "develop"
+
+[[orgs]]
+name = "owner"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..develop (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn report_uses_the_highest_semver_tag_as_baseline_in_tag_mode() {
     // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/repos/owner/repo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _tags_mock = server
+        .mock("GET", "/repos/owner/repo-a/tags")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(r#"[{"name": "v1.9.0"}, {"name": "v1.10.0"}, {"name": "nightly"}]"#)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.10.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
     let fx = Fixture::new();
-    let mut cmd = fx.cmd(["report", "--help"]);
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+baseline = "tag"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
 
     // WHEN
     // THEN
-    assert_cmd_snapshot!(cmd, @r"
-    success: true
-    exit_code: 0
-    ----- stdout -----
-    Show unreleased commits for repos
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.10.0..main (2 commits)
 
-    Usage: unreleased report [OPTIONS]
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
 
-    Options:
-      -c, --config-path <PATH>      Path to the unreleased's file (defaults to <YOUR_CONFIG_DIR>/unreleased/unreleased.toml)
-          --debug                   Output debug information without doing anything
-      -f, --filter <REGEX>          Regex to use for filtering repos
-      -o, --output-format <FORMAT>  Output format [default: stdout] [possible values: stdout, html]
-          --stdout-plain            Whether to output text to stdout without color
-          --html-output <PATH>      Path for the HTML output file [default: unreleased.html]
-          --html-title <STRING>     Title for HTML report [default: unreleased]
-          --html-template <PATH>    Path to custom HTML template file
-      -h, --help                    Print help
 
-    ----- stderr -----
-    ");
+        ----- stderr -----
+        ");
+    });
 }
 
 #[test]
-fn debug_flag_works_for_defaults() {
+fn report_shows_a_sub_section_per_head_ref_compared_against_its_release_line() {
     // GIVEN
+    let mut server = mockito::Server::new();
+    let _tags_mock = server
+        .mock("GET", "/repos/owner/repo-a/tags")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(r#"[{"name": "v2.1.0"}, {"name": "v1.4.2"}, {"name": "v1.4.1"}]"#)
+        .expect(2)
+        .create();
+    let _main_compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v2.1.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+    let _release_compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.4.2...release/1.x")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
     let fx = Fixture::new();
-    let mut cmd = fx.cmd(["report", "--debug"]);
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+baseline = "tag"
+head_refs = ["main", {{ ref = "release/1.x", tag_pattern = '^v1\.' }}]
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
 
     // WHEN
     // THEN
-    assert_cmd_snapshot!(cmd, @r"
-    success: true
-    exit_code: 0
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a
+
+          main v2.1.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+          release/1.x v1.4.2..release/1.x (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn report_shows_a_section_per_package_with_commits_touching_its_paths() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/repos/owner/repo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _tags_mock = server
+        .mock("GET", "/repos/owner/repo-a/tags")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(r#"[{"name": "api/v1.4.0"}, {"name": "web/v2.1.3"}, {"name": "web/v2.0.0"}]"#)
+        .create();
+    let _api_compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/api/v1.4.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+    let _web_compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/web/v2.1.3...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+    let _api_path_mock = server
+        .mock("GET", "/repos/owner/repo-a/commits")
+        .match_query(Matcher::UrlEncoded("path".into(), "services/api".into()))
+        .with_header("content-type", "application/json")
+        .with_body(r#"[{"sha": "2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e"}]"#)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+
+[[repos.packages]]
+name = "api"
+tag_prefix = "api/"
+paths = ["services/api"]
+
+[[repos.packages]]
+name = "web"
+tag_prefix = "web/"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a [api] api/v1.4.0..main (1 commit)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+
+        owner/repo-a [web] web/v2.1.3..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn package_logs_count_only_their_own_commits_when_the_compare_endpoint_returns_only_some_commits() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _tags_mock = server
+        .mock("GET", "/repos/owner/repo-a/tags")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(r#"[{"name": "api/v1.4.0"}]"#)
+        .create();
+    let _api_compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/api/v1.4.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE.replace(r#""total_commits": 2"#, r#""total_commits": 1500"#))
+        .create();
+    let _api_path_mock = server
+        .mock("GET", "/repos/owner/repo-a/commits")
+        .match_query(Matcher::UrlEncoded("path".into(), "services/api".into()))
+        .with_header("content-type", "application/json")
+        .with_body(r#"[{"sha": "2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e"}]"#)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+
+[[repos.packages]]
+name = "api"
+tag_prefix = "api/"
+paths = ["services/api"]
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a [api] api/v1.4.0..main (at least 1 commit)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn report_shows_repos_that_have_never_been_released() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/repos/owner/repo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_status(404)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"message": "Not Found"}"#)
+        .create();
+    let _commits_mock = server
+        .mock("GET", "/repos/owner/repo-a/commits")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("sha".into(), "main".into()),
+            Matcher::UrlEncoded("per_page".into(), "1".into()),
+        ]))
+        .with_header("content-type", "application/json")
+        .with_body(COMMITS_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--never-released-commits",
+        "1",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        never released
+
+        owner/repo-a main (no releases yet, showing 1 recent commit)
+
+         9b1f0c2  add readme  User C  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn report_with_keep_going_includes_errors_and_exits_with_a_distinct_code() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_a_mock = server
+        .mock("GET", "/repos/owner/repo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _repo_b_mock = server
+        .mock("GET", "/repos/owner/repo-b")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_status(404)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"message": "Not Found"}"#)
+        .create();
+    let _commits_mock = server
+        .mock("GET", "/repos/owner/repo-a/commits")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("sha".into(), "main".into()),
+            Matcher::UrlEncoded("per_page".into(), "1".into()),
+        ]))
+        .with_header("content-type", "application/json")
+        .with_body(COMMITS_RESPONSE)
+        .create();
+    let _failing_release_mock = server
+        .mock("GET", "/repos/owner/repo-b/releases/latest")
+        .with_status(500)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"message": "Server Error"}"#)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+
+[[repos]]
+repo = "owner/repo-b"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--never-released-commits",
+        "1",
+        "--keep-going",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r#"
+        success: false
+        exit_code: 3
+        ----- stdout -----
+        never released
+
+        owner/repo-a main (no releases yet, showing 1 recent commit)
+
+         9b1f0c2  add readme  User C  [N]d ago 
+
+        errors
+
+         - owner/repo-b: couldn't get the latest release: GitHub API request failed with status 500 Internal Server Error: {"message": "Server Error"}
+
+
+        ----- stderr -----
+        couldn't fetch commit logs for some repos:
+         - owner/repo-b: couldn't get the latest release: GitHub API request failed with status 500 Internal Server Error: {"message": "Server Error"}
+        "#);
+    });
+}
+
+#[test]
+fn keep_going_can_be_enabled_via_config() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/repos/owner/repo-b")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _failing_release_mock = server
+        .mock("GET", "/repos/owner/repo-b/releases/latest")
+        .with_status(500)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"message": "Server Error"}"#)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+keep_going = true
+
+[[repos]]
+repo = "owner/repo-b"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+    ]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r#"
+    success: false
+    exit_code: 3
     ----- stdout -----
-    DEBUG INFO
+    errors
 
-    command:                report
-    config file path:       <NOT PROVIDED>
-    repo filter:            <NOT PROVIDED>
-    output format:          stdout
-    plain output:           false
+     - owner/repo-b: couldn't get the latest release: GitHub API request failed with status 500 Internal Server Error: {"message": "Server Error"}
 
 
     ----- stderr -----
-    ");
+    couldn't fetch commit logs for some repos:
+     - owner/repo-b: couldn't get the latest release: GitHub API request failed with status 500 Internal Server Error: {"message": "Server Error"}
+    "#);
 }
 
 #[test]
-fn debug_flag_works_with_overridden_flags_for_stdout_output() {
+fn report_shows_progress_and_failures_on_stderr_when_asked_to() {
     // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/repos/owner/repo-b")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _failing_release_mock = server
+        .mock("GET", "/repos/owner/repo-b/releases/latest")
+        .with_status(500)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"message": "Server Error"}"#)
+        .create();
+
     let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+keep_going = true
+
+[[repos]]
+repo = "owner/repo-b"
+"#,
+            server.url()
+        ),
+    );
     let mut cmd = fx.cmd([
         "report",
         "--config-path",
-        "tests/assets/valid-config.toml",
-        "--debug",
-        "--filter",
-        "repo-(a|b)",
-        "--output-format",
-        "stdout",
+        &config_path.to_string_lossy(),
         "--stdout-plain",
+        "--progress",
     ]);
 
     // WHEN
     // THEN
-    assert_cmd_snapshot!(cmd, @r"
-    success: true
-    exit_code: 0
+    assert_cmd_snapshot!(cmd, @r#"
+    success: false
+    exit_code: 3
     ----- stdout -----
-    DEBUG INFO
+    errors
 
-    command:                report
-    config file path:       tests/assets/valid-config.toml
-    repo filter:            repo-(a|b)
-    output format:          stdout
-    plain output:           true
+     - owner/repo-b: couldn't get the latest release: GitHub API request failed with status 500 Internal Server Error: {"message": "Server Error"}
 
 
     ----- stderr -----
-    ");
+    fetched 0/1 repos
+    fetched 0/1 repos; in flight: owner/repo-b
+    failed: owner/repo-b: couldn't get the latest release: GitHub API request failed with status 500 Internal Server Error: {"message": "Server Error"}
+    fetched 1/1 repos
+    couldn't fetch commit logs for some repos:
+     - owner/repo-b: couldn't get the latest release: GitHub API request failed with status 500 Internal Server Error: {"message": "Server Error"}
+    "#);
 }
 
 #[test]
-fn debug_flag_works_with_overridden_flags_for_html_output() {
+fn quiet_flag_hides_the_remaining_api_quota() {
     // GIVEN
+    let mut server = mockito::Server::new();
+    let _release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_header("x-ratelimit-limit", "5000")
+        .with_header("x-ratelimit-remaining", "4998")
+        .with_header("x-ratelimit-reset", "1737028860")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_header("x-ratelimit-limit", "5000")
+        .with_header("x-ratelimit-remaining", "4997")
+        .with_header("x-ratelimit-reset", "1737028860")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
     let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+head_ref = "main"
+"#,
+            server.url()
+        ),
+    );
     let mut cmd = fx.cmd([
         "report",
         "--config-path",
-        "tests/assets/valid-config.toml",
-        "--debug",
-        "--filter",
-        "repo-(a|b)",
+        &config_path.to_string_lossy(),
+        "--stdout-plain",
+        "--quiet",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r"\d+d ago", "[N]d ago")]}, {
+        assert_cmd_snapshot!(cmd, @r"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        owner/repo-a v1.0.0..main (2 commits)
+
+         2ff3e97  allow configuring table style  User B  [N]d ago 
+         ae7de14  add tracing support            User A  [N]d ago 
+
+
+        ----- stderr -----
+        ");
+    });
+}
+
+#[test]
+fn report_can_be_output_as_json() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/repos/owner/repo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+"#,
+            server.url()
+        ),
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
         "--output-format",
-        "html",
-        "--html-output",
-        "output.html",
-        "--html-template",
-        "tests/assets/template.html",
-        "--html-title",
-        "unreleased code",
+        "json",
+    ]);
+
+    // WHEN
+    // THEN
+    insta::with_settings!({filters => vec![(r#""generated_at": "[^"]+""#, r#""generated_at": "[TIMESTAMP]""#)]}, {
+        assert_cmd_snapshot!(cmd, @r#"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+        {
+          "schema_version": 1,
+          "generated_at": "[TIMESTAMP]",
+          "commit_logs": [
+            {
+              "repo": "owner/repo-a",
+              "group": null,
+              "label": null,
+              "package": null,
+              "base_ref": "v1.0.0",
+              "head_ref": "main",
+              "compare_url": "https://github.example.com/owner/repo-a/compare/v1.0.0...main",
+              "total_commits": 2,
+              "ahead_by": 2,
+              "behind_by": 0,
+              "truncated": false,
+              "total_is_lower_bound": false,
+              "commits": [
+                {
+                  "sha": "2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e",
+                  "message": "allow configuring table style\n\nsome more details",
+                  "author": "User B",
+                  "date": "2025-01-16T11:00:00Z",
+                  "url": "https://github.example.com/owner/repo-a/commit/2ff3e97a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e"
+                },
+                {
+                  "sha": "ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5",
+                  "message": "add tracing support",
+                  "author": "User A",
+                  "date": "2025-01-15T10:00:00Z",
+                  "url": "https://github.example.com/owner/repo-a/commit/ae7de14c1d5e8c1b1a2b3c4d5e6f708192a3b4c5"
+                }
+              ]
+            }
+          ],
+          "never_released_logs": [],
+          "errors": []
+        }
+
+        ----- stderr -----
+        "#);
+    });
+}
+
+#[test]
+fn report_can_be_rendered_using_a_custom_template() {
+    // GIVEN
+    let mut server = mockito::Server::new();
+    let _repo_mock = server
+        .mock("GET", "/repos/owner/repo-a")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"default_branch": "main"}"#)
+        .create();
+    let _release_mock = server
+        .mock("GET", "/repos/owner/repo-a/releases/latest")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"tag_name": "v1.0.0", "draft": false}"#)
+        .create();
+    let _compare_mock = server
+        .mock("GET", "/repos/owner/repo-a/compare/v1.0.0...main")
+        .match_query(Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(COMPARE_RESPONSE)
+        .create();
+
+    let fx = Fixture::new();
+    let config_path = fx.write_file(
+        "unreleased.toml",
+        &format!(
+            r#"
+api_base_url = "{}"
+
+[[repos]]
+repo = "owner/repo-a"
+"#,
+            server.url()
+        ),
+    );
+    let template_path = fx.write_file(
+        "slack.txt",
+        r#"{% for log in commit_logs %}*{{ log.repo }}* has {{ log.total_commits }} unreleased commits by {% for author in log.authors %}{{ author.name }}{% if not loop.last %}, {% endif %}{% endfor %}
+{% endfor %}"#,
+    );
+    let mut cmd = fx.cmd([
+        "report",
+        "--config-path",
+        &config_path.to_string_lossy(),
+        "--output-format",
+        "template",
+        "--template",
+        &template_path.to_string_lossy(),
     ]);
 
     // WHEN
@@ -125,16 +3006,7 @@ fn debug_flag_works_with_overridden_flags_for_html_output() {
     success: true
     exit_code: 0
     ----- stdout -----
-    DEBUG INFO
-
-    command:                report
-    config file path:       tests/assets/valid-config.toml
-    repo filter:            repo-(a|b)
-    output format:          html
-    output path:            output.html
-    title:                  unreleased code
-    template path:          tests/assets/template.html
-
+    *owner/repo-a* has 2 unreleased commits by User A, User B
 
     ----- stderr -----
     ");
@@ -240,13 +3112,25 @@ fn fails_if_provided_with_config_with_invalid_data() {
 
     Caused by:
         config has errors:
-         - repo #1 has errors:
+         - repo #1 has errors (tests/assets/invalid-data.toml, line 4, column 1):
+             |
+           4 | [[repos]]
+             | ^^^^^^^^^
            - repo needs to be in the format "owner/repo"
-         - repo #2 has errors:
+         - repo #2 has errors (tests/assets/invalid-data.toml, line 7, column 1):
+             |
+           7 | [[repos]]
+             | ^^^^^^^^^
            - repo name is empty
-         - repo #3 has errors:
+         - repo #3 has errors (tests/assets/invalid-data.toml, line 10, column 1):
+              |
+           10 | [[repos]]
+              | ^^^^^^^^^
            - owner is empty
-         - repo #4 has errors:
+         - repo #4 has errors (tests/assets/invalid-data.toml, line 13, column 1):
+              |
+           13 | [[repos]]
+              | ^^^^^^^^^
            - repo needs to be in the format "owner/repo"
            - head_ref is empty
     "#);
@@ -334,3 +3218,26 @@ fn fails_if_provided_with_absent_html_template_file() {
         No such file or directory (os error 2)
     "#);
 }
+
+#[test]
+fn fails_if_template_output_format_is_used_without_a_template() {
+    // GIVEN
+    let fx = Fixture::new();
+    let mut cmd = fx.cmd(["report", "--output-format", "template"]);
+
+    // WHEN
+    // THEN
+    assert_cmd_snapshot!(cmd, @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the following required arguments were not provided:
+      --template <PATH>
+
+    Usage: unreleased report --output-format <FORMAT> --template <PATH>
+
+    For more information, try '--help'.
+    ");
+}
@@ -25,6 +25,8 @@ fn shows_help() {
 
     Commands:
       report  Show unreleased commits for repos
+      config  Manage unreleased's config file
+      cache   Manage the cache of API responses
       help    Print this message or the help of the given subcommand(s)
 
     Options: